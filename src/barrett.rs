@@ -0,0 +1,103 @@
+use crate::modint::ModInt;
+
+/// Barrett reduction
+///
+/// `floor(2^64 / m)`を前計算しておき、剰余を乗算とシフトで求める
+///
+/// Montgomery乗算と違いmodが偶数でも使える
+#[derive(Debug, Clone, Copy)]
+pub struct BarrettReducer {
+    modulus: u32,
+    /// `floor((2^64 - 1) / m)`
+    im: u64,
+    /// `2^64 mod m`
+    r64: u64,
+}
+
+impl BarrettReducer {
+    pub fn new(modulus: u32) -> Self {
+        assert!(modulus != 0, "modulus must be positive");
+        let m = modulus as u64;
+        let im = std::u64::MAX / m;
+        let r64 = (std::u64::MAX % m + 1) % m;
+        Self { modulus, im, r64 }
+    }
+
+    pub fn modulus(&self) -> u32 {
+        self.modulus
+    }
+
+    /// `x mod m` (`x < 2^64`)
+    fn reduce_u64(&self, x: u64) -> u64 {
+        let m = self.modulus as u64;
+        let q = ((x as u128 * self.im as u128) >> 64) as u64;
+        let mut r = x - q * m;
+        // `im`の切り捨て分で`q`は真の商より高々2小さい
+        while r >= m {
+            r -= m;
+        }
+        r
+    }
+
+    /// `x mod m`
+    pub fn reduce(&self, x: u128) -> u64 {
+        let hi = self.reduce_u64((x >> 64) as u64);
+        let lo = self.reduce_u64(x as u64);
+        // m < 2^32 なので hi * r64 < 2^64
+        self.reduce_u64(self.reduce_u64(hi * self.r64) + lo)
+    }
+
+    /// `a * b mod m`
+    pub fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    /// `ModInt`どうしの積を除算命令を使わずに
+    ///
+    /// # Panic
+    /// `a`, `b`のmodが`self`と異なる時
+    pub fn mul_mint(&self, a: ModInt, b: ModInt) -> ModInt {
+        let m = self.modulus as usize;
+        assert!(
+            a.get_mod() == m && b.get_mod() == m,
+            "modulus mismatch: reducer mod {} vs operands mod {}, {}",
+            m,
+            a.get_mod(),
+            b.get_mod()
+        );
+        ModInt::new(self.mul(a.get() as u64, b.get() as u64), self.modulus)
+    }
+}
+
+#[test]
+fn barrett_reduce_test() {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    let mut rng = SmallRng::seed_from_u64(391);
+    for &m in &[1, 2, 3, 1 << 20, 998_244_353, 1_000_000_007, std::u32::MAX] {
+        let br = BarrettReducer::new(m);
+        for _ in 0..1000 {
+            let x: u128 = rng.gen();
+            assert_eq!(br.reduce(x) as u128, x % m as u128);
+        }
+        assert_eq!(
+            br.reduce(std::u128::MAX) as u128,
+            std::u128::MAX % m as u128
+        );
+        assert_eq!(br.reduce(0), 0);
+    }
+}
+
+#[test]
+fn barrett_mul_test() {
+    let m = 1_000_000_000; // 偶数のmod
+    let br = BarrettReducer::new(m);
+    let a = ModInt::new(999_999_999, m);
+    let b = ModInt::new(123_456_789, m);
+    assert_eq!(br.mul_mint(a, b), a * b);
+    let mut acc = ModInt::new(1, m);
+    for i in 1..100 {
+        acc = br.mul_mint(acc, ModInt::new(i, m));
+    }
+    assert_eq!(acc, (1..100).fold(ModInt::new(1, m), |a, i| a * i));
+}
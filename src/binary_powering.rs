@@ -27,6 +27,100 @@ pub fn binary_powering(n: usize, r: usize, m: usize) -> usize {
     res
 }
 
+/// `a^(b^(c^...)) mod m` (`bases = [a, b, c, ...]`、空なら1)
+///
+/// 一般化されたオイラーの定理 `e >= phi(m)` なら `a^e = a^(e mod phi(m) + phi(m))` を塔の上へ繰り返し使う
+///
+/// 指数が`phi(m)`未満かどうかは、塔の値を`u64::MAX`で飽和させて別に求めて判定する
+///
+/// `phi`のために試し割りするので、段ごとに`O(sqrt m)`
+pub fn power_tower(bases: &[u64], modulus: u64) -> u64 {
+    assert!(modulus != 0, "modulus must not be 0");
+    tower_mod(bases, modulus)
+}
+
+/// `bases`の塔の値、`u64::MAX`以上なら`u64::MAX`
+fn tower_saturated(bases: &[u64]) -> u64 {
+    let (&a, rest) = match bases.split_first() {
+        Some(x) => x,
+        None => return 1,
+    };
+    let e = tower_saturated(rest);
+    match a {
+        0 => (e == 0) as u64,
+        1 => 1,
+        _ if e >= 64 => std::u64::MAX,
+        _ => a.checked_pow(e as u32).unwrap_or(std::u64::MAX),
+    }
+}
+
+fn tower_mod(bases: &[u64], m: u64) -> u64 {
+    let (&a, rest) = match bases.split_first() {
+        Some(x) => x,
+        None => return 1 % m,
+    };
+    let phi = crate::prime::trial_division::factorization(m as usize)
+        .into_iter()
+        .fold(m, |acc, (p, _)| acc / p as u64 * (p as u64 - 1));
+    let e = tower_saturated(rest);
+    let e = if e < phi {
+        e
+    } else {
+        tower_mod(rest, phi) + phi
+    };
+    pow_mod_u64(a % m, e, m)
+}
+
+fn pow_mod_u64(mut a: u64, mut e: u64, m: u64) -> u64 {
+    let mut res = 1 % m;
+    while e > 0 {
+        if e & 1 != 0 {
+            res = (res as u128 * a as u128 % m as u128) as u64;
+        }
+        a = (a as u128 * a as u128 % m as u128) as u64;
+        e >>= 1;
+    }
+    res
+}
+
+#[test]
+fn power_tower_test() {
+    let direct = |bases: &[u64], m: u64| {
+        let e = bases[1..]
+            .iter()
+            .rev()
+            .fold(1u64, |acc, &b| b.pow(acc as u32));
+        (bases[0] as u128).pow(e as u32) % m as u128
+    };
+    // 3段の塔を直接計算と比較
+    for a in 0..5 {
+        for b in 0..4 {
+            for c in 0..3 {
+                for &m in &[1, 2, 6, 7, 12, 16, 100, 1_000_000_007] {
+                    let bases = [a, b, c];
+                    assert_eq!(
+                        power_tower(&bases, m) as u128,
+                        direct(&bases, m),
+                        "{:?} mod {}",
+                        bases,
+                        m
+                    );
+                }
+            }
+        }
+    }
+    assert_eq!(power_tower(&[2, 3, 2], 1000), 512);
+    assert_eq!(power_tower(&[], 10), 1);
+    assert_eq!(power_tower(&[7], 10), 7);
+    // 2^2^2^2^2 = 2^65536 ≡ 736 (mod 1000)
+    assert_eq!(power_tower(&[2, 2, 2, 2, 2], 1000), 736);
+    // 十分高い塔の値はmodごとに一定になる
+    assert_eq!(
+        power_tower(&[3; 20], 1_000_000_007),
+        power_tower(&[3; 30], 1_000_000_007)
+    );
+}
+
 #[test]
 fn pow_test() {
     assert_eq!(9, binary_powering(3, 2, 10000));
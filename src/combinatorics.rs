@@ -2,6 +2,18 @@ pub mod factorial;
 
 pub mod binomial_coefficient;
 
+// 二項係数の累積和
+pub mod binom_prefix_sum;
+
+// 二項変換
+pub mod binomial_transform;
+
+// 二項係数のメモ化
+pub mod comb_cache;
+
+// 全射の数、重複組み合わせの数、格子経路の数
+pub mod counting;
+
 // 順列
 pub mod permutation;
 // 組み合わせ
@@ -11,3 +23,31 @@ pub mod combination;
 
 // for snippet
 pub mod combinatorics;
+
+/// `use sfcpl::combinatorics::*;` で組合せ系のヘルパーとFactoriableをまとめて使えるようにする
+pub mod prelude {
+    pub use super::binom_prefix_sum::{binom_prefix_sum, BinomPrefixSum};
+    pub use super::binomial_coefficient::{
+        BCTSmallNK, BCTholdN, BinomialCoefficient, PartialBinomialCoefficient, BCTDP,
+    };
+    pub use super::binomial_transform::{binomial_transform, inverse_binomial_transform};
+    pub use super::comb_cache::CombCache;
+    pub use super::combination::{combination, combination_with_table, generalized_comb};
+    pub use super::counting::{catalan_paths, lattice_paths, stars_and_bars, surjections};
+    pub use super::factorial::{Factoriable, FallingFactorial, RisingFactorial};
+    pub use super::permutation::{permutation, permutation_with_table};
+}
+
+pub use prelude::*;
+
+#[test]
+fn prelude_test() {
+    use crate::combinatorics::*;
+    use crate::modint::ModInt;
+
+    let tbl = BCTDP::new(10, 1_000_000_007);
+    assert_eq!(tbl.binomial(5, 2).get(), 10);
+    assert_eq!(permutation_with_table(&tbl, 5, 2).get(), 20);
+    assert_eq!(combination(ModInt::new(5, 1_000_000_007), 2).get(), 10);
+    assert_eq!(5usize.factorial(), 120);
+}
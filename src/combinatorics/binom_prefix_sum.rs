@@ -0,0 +1,141 @@
+pub use binom_prefix_sum::*;
+
+pub mod binom_prefix_sum {
+    use crate::combinatorics::binomial_coefficient::{BinomialCoefficient, BCTDP};
+    use crate::modint::ModInt;
+    use num_traits::Pow;
+
+    /// `nC0 + nC1 + ... + nCr mod m`
+    ///
+    /// `r >= n`なら`2^n`
+    ///
+    /// `O(n)`、modは`n`より大きい素数であること
+    pub fn binom_prefix_sum(n: usize, r: usize, modulus: u32) -> ModInt {
+        if r >= n {
+            return ModInt::new(2, modulus).pow(n);
+        }
+        let tbl = BCTDP::new(n, modulus as usize);
+        (0..=r).map(|k| tbl.binomial(n, k)).sum()
+    }
+
+    /// `S(n, r) = nC0 + ... + nCr` を`n`, `r`を1ずつ動かしながら保持する
+    ///
+    /// `S(n + 1, r) = 2S(n, r) - nCr` を使うので各操作 `O(1)`
+    ///
+    /// クエリを並び替えて(Mo's algorithm)まとめて答えるときに
+    pub struct BinomPrefixSum {
+        table: BCTDP,
+        max_n: usize,
+        n: usize,
+        r: usize,
+        sum: ModInt,
+        inv2: ModInt,
+    }
+
+    impl BinomPrefixSum {
+        /// `S(0, 0) = 1` から始める
+        ///
+        /// `max_n`は扱う`n`の上限、modは`max_n`より大きい奇素数であること
+        pub fn new(max_n: usize, modulus: u32) -> Self {
+            let table = BCTDP::new(max_n.max(2), modulus as usize);
+            let inv2 = table.inv(2);
+            Self {
+                table,
+                max_n,
+                n: 0,
+                r: 0,
+                sum: ModInt::new(1, modulus),
+                inv2,
+            }
+        }
+
+        pub fn n(&self) -> usize {
+            self.n
+        }
+
+        pub fn r(&self) -> usize {
+            self.r
+        }
+
+        /// 現在の`S(n, r)`
+        pub fn get(&self) -> ModInt {
+            self.sum
+        }
+
+        /// # Panic
+        /// `n == max_n`の時
+        pub fn inc_n(&mut self) {
+            assert!(self.n < self.max_n, "n is already max_n");
+            self.sum = self.sum * 2 - self.table.binomial(self.n, self.r);
+            self.n += 1;
+        }
+
+        /// # Panic
+        /// `n == 0`の時
+        pub fn dec_n(&mut self) {
+            assert!(self.n > 0, "n is already 0");
+            self.n -= 1;
+            self.sum = (self.sum + self.table.binomial(self.n, self.r)) * self.inv2;
+        }
+
+        pub fn inc_r(&mut self) {
+            self.r += 1;
+            self.sum += self.table.binomial(self.n, self.r);
+        }
+
+        /// # Panic
+        /// `r == 0`の時
+        pub fn dec_r(&mut self) {
+            assert!(self.r > 0, "r is already 0");
+            self.sum -= self.table.binomial(self.n, self.r);
+            self.r -= 1;
+        }
+
+        /// `S(n, r)`まで移動してその値を返す
+        pub fn move_to(&mut self, n: usize, r: usize) -> ModInt {
+            while self.r < r {
+                self.inc_r();
+            }
+            while self.n < n {
+                self.inc_n();
+            }
+            while self.n > n {
+                self.dec_n();
+            }
+            while self.r > r {
+                self.dec_r();
+            }
+            self.sum
+        }
+    }
+
+    #[test]
+    fn binom_prefix_sum_test() {
+        let m = 1_000_000_007;
+        // 1 + 10 + 45 + 120
+        assert_eq!(binom_prefix_sum(10, 3, m).get(), 176);
+        assert_eq!(binom_prefix_sum(10, 0, m).get(), 1);
+        assert_eq!(binom_prefix_sum(10, 9, m).get(), 1023);
+
+        // r >= n
+        assert_eq!(binom_prefix_sum(10, 10, m).get(), 1024);
+        assert_eq!(binom_prefix_sum(10, 100, m).get(), 1024);
+        assert_eq!(binom_prefix_sum(0, 0, m).get(), 1);
+    }
+
+    #[test]
+    fn binom_prefix_sum_sliding_test() {
+        let m = 1_000_000_007;
+        let mut s = BinomPrefixSum::new(30, m);
+        let queries = [(10, 3), (20, 5), (4, 2), (30, 30), (15, 0), (7, 10)];
+        for &(n, r) in &queries {
+            assert_eq!(s.move_to(n, r), binom_prefix_sum(n, r, m));
+            assert_eq!((s.n(), s.r()), (n, r));
+        }
+
+        // max_n まではたどれるが、それを越えるとpanic
+        let mut s = BinomPrefixSum::new(3, m);
+        assert_eq!(s.move_to(3, 1).get(), 4);
+        assert!(std::panic::catch_unwind(move || s.inc_n()).is_err());
+    }
+}
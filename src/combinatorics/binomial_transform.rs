@@ -0,0 +1,73 @@
+pub use binomial_transform::*;
+
+pub mod binomial_transform {
+    use crate::combinatorics::binomial_coefficient::{BinomialCoefficient, BCTDP};
+    use crate::modint::ModInt;
+
+    /// 二項変換 `b[n] = sum_k C(n, k) a[k]`
+    ///
+    /// `O(n^2)`、modは`a.len()`より大きい素数であること
+    pub fn binomial_transform(a: &[ModInt]) -> Vec<ModInt> {
+        transform(a, false)
+    }
+
+    /// 二項変換の逆 `b[n] = sum_k (-1)^(n - k) C(n, k) a[k]`
+    ///
+    /// `O(n^2)`、modは`a.len()`より大きい素数であること
+    pub fn inverse_binomial_transform(a: &[ModInt]) -> Vec<ModInt> {
+        transform(a, true)
+    }
+
+    fn transform(a: &[ModInt], inverse: bool) -> Vec<ModInt> {
+        let m = match a.first() {
+            Some(x) => x.get_mod(),
+            None => return Vec::new(),
+        };
+        let tbl = BCTDP::new(a.len(), m);
+        (0..a.len())
+            .map(|n| {
+                (0..=n).fold(ModInt::new(0, m), |acc, k| {
+                    let t = tbl.binomial(n, k) * a[k];
+                    if inverse && (n - k) % 2 == 1 {
+                        acc - t
+                    } else {
+                        acc + t
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn binomial_transform_test() {
+    use crate::modint::ModInt;
+    let m = 1_000_000_007;
+    let a = [3, -1, 4, 1, -5, 9, 2]
+        .iter()
+        .map(|&x| ModInt::new(x, m))
+        .collect::<Vec<_>>();
+    assert_eq!(inverse_binomial_transform(&binomial_transform(&a)), a);
+    assert_eq!(binomial_transform(&inverse_binomial_transform(&a)), a);
+
+    // 全て1の列は2^n に
+    let ones = vec![ModInt::new(1, m); 10];
+    let b = binomial_transform(&ones);
+    for (n, x) in b.iter().enumerate() {
+        assert_eq!(x.get(), 1 << n);
+    }
+    // 完全順列の数 = n! の逆二項変換
+    let fact = (0..8)
+        .scan(1i64, |f, i| {
+            let r = *f;
+            *f *= i + 1;
+            Some(ModInt::new(r, m))
+        })
+        .collect::<Vec<_>>();
+    let d = inverse_binomial_transform(&fact);
+    assert_eq!(
+        d.iter().map(|x| x.get()).collect::<Vec<_>>(),
+        vec![1, 0, 1, 2, 9, 44, 265, 1854]
+    );
+    assert!(binomial_transform(&[]).is_empty());
+}
@@ -0,0 +1,91 @@
+pub use comb_cache::*;
+
+pub mod comb_cache {
+    use crate::combinatorics::binomial_coefficient::{BinomialCoefficient, BCTDP};
+    use crate::modint::ModInt;
+    use std::collections::HashMap;
+
+    /// `n C k` の問い合わせ結果をメモ化する
+    ///
+    /// 階乗のテーブルは必要になった分だけ(倍々に)伸ばすので、
+    /// 現れる`n`が少ないときに巨大なテーブルを最初に作らなくて良い
+    ///
+    /// modは素数であること、`n < mod`であること(階乗のテーブルが0を含むため)
+    pub struct CombCache {
+        modulo: usize,
+        /// `table`で扱える`n`の最大値
+        size: usize,
+        table: BCTDP,
+        cache: HashMap<(u64, u64), ModInt>,
+        hits: usize,
+    }
+
+    impl CombCache {
+        pub fn new(modulo: usize) -> Self {
+            Self {
+                modulo,
+                size: 1,
+                table: BCTDP::new(1, modulo),
+                cache: HashMap::new(),
+                hits: 0,
+            }
+        }
+
+        /// `n C k`
+        ///
+        /// # Panic
+        /// `k <= n`かつ`n >= mod`の時
+        pub fn comb(&mut self, n: u64, k: u64) -> ModInt {
+            if let Some(&c) = self.cache.get(&(n, k)) {
+                self.hits += 1;
+                return c;
+            }
+            let c = if k > n {
+                ModInt::new(0, self.modulo)
+            } else {
+                let n = n as usize;
+                assert!(n < self.modulo, "n must be less than the modulus");
+                if n > self.size {
+                    self.size = n.max(self.size * 2);
+                    self.table = BCTDP::new(self.size, self.modulo);
+                }
+                self.table.binomial(n, k as usize)
+            };
+            self.cache.insert((n, k), c);
+            c
+        }
+
+        /// キャッシュに当たった回数
+        pub fn hits(&self) -> usize {
+            self.hits
+        }
+    }
+
+    #[test]
+    fn comb_cache_test() {
+        use num_integer::binomial;
+
+        let mut cache = CombCache::new(1_000_000_007);
+        let a = cache.comb(10, 3);
+        assert_eq!(a.get(), 120);
+        assert_eq!(cache.hits(), 0);
+        let b = cache.comb(10, 3);
+        assert_eq!(a, b);
+        assert_eq!(cache.hits(), 1);
+
+        // テーブルを伸ばしても以前の結果と一致する
+        assert_eq!(
+            cache.comb(50, 25).get(),
+            (binomial(50u64, 25) % 1_000_000_007) as i64
+        );
+        assert_eq!(cache.comb(10, 3), a);
+        assert_eq!(cache.hits(), 2);
+
+        assert_eq!(cache.comb(3, 5).get(), 0);
+
+        // n >= mod は扱えない
+        let mut small = CombCache::new(7);
+        assert_eq!(small.comb(6, 3).get(), 20 % 7);
+        assert!(std::panic::catch_unwind(move || small.comb(7, 3)).is_err());
+    }
+}
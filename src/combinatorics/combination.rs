@@ -3,7 +3,7 @@ pub use combination::*;
 pub mod combination {
     use crate::modint::ModInt;
 
-    use crate::combinatorics::binomial_coefficient::BinomialCoefficient;
+    use crate::combinatorics::binomial_coefficient::{BinomialCoefficient, BCTDP};
     use crate::combinatorics::factorial::Factoriable;
     use crate::combinatorics::permutation::permutation;
 
@@ -17,6 +17,31 @@ pub mod combination {
     pub fn combination_with_table<T: BinomialCoefficient>(table: &T, n: usize, k: usize) -> ModInt {
         table.binomial(n, k)
     }
+
+    /// 負の`n`にも拡張した二項係数 `n(n - 1)...(n - k + 1) / k!`
+    ///
+    /// `n < 0`なら`C(n, k) = (-1)^k C(-n + k - 1, k)`
+    ///
+    /// 母関数`(1 - x)^n`の展開などに
+    ///
+    /// `O(k)`、modは`k`より大きい素数であること
+    pub fn generalized_comb(n: i64, k: usize, modulus: u32) -> ModInt {
+        let tbl = BCTDP::new(k, modulus as usize);
+        let falling = |top: i128| {
+            (0..k as i128).fold(ModInt::new(1, modulus), |acc, i| {
+                acc * ModInt::new(((top - i) % modulus as i128) as i64, modulus)
+            })
+        };
+        if n >= 0 {
+            return falling(n as i128) * tbl.factorial_inverse(k);
+        }
+        let res = falling(-(n as i128) + k as i128 - 1) * tbl.factorial_inverse(k);
+        if k % 2 == 0 {
+            res
+        } else {
+            -res
+        }
+    }
 }
 
 #[test]
@@ -62,3 +87,32 @@ fn comb_tbl_test() {
     );
     assert_eq!(combination_with_table(&tbl, 2, 0).get(), binomial(2, 0));
 }
+
+#[test]
+fn generalized_comb_test() {
+    use crate::modint::ModInt;
+    use num_integer::binomial;
+    let m = 1_000_000_007;
+    assert_eq!(generalized_comb(-1, 3, m).get(), m as i64 - 1);
+    // (1 - x)^(-1) = 1 + x + x^2 + ...  の係数は (-1)^k C(-1, k) = 1
+    for k in 0..10 {
+        let c = generalized_comb(-1, k, m);
+        assert_eq!(if k % 2 == 0 { c } else { -c }.get(), 1);
+    }
+    // C(-3, 2) = 6
+    assert_eq!(generalized_comb(-3, 2, m).get(), 6);
+    // C(-2, 3) = -4
+    assert_eq!(generalized_comb(-2, 3, m).get(), m as i64 - 4);
+    for n in 0..15 {
+        for k in 0..15 {
+            assert_eq!(
+                generalized_comb(n, k, m).get(),
+                binomial(n as i64, k as i64) * (n >= k as i64) as i64
+            );
+        }
+    }
+    assert_eq!(
+        generalized_comb(std::i64::MIN, 1, m),
+        ModInt::new(std::i64::MIN, m)
+    );
+}
@@ -0,0 +1,148 @@
+pub use counting::*;
+
+pub mod counting {
+    use crate::combinatorics::binomial_coefficient::{BinomialCoefficient, BCTDP};
+    use crate::modint::ModInt;
+    use num_traits::Pow;
+
+    /// `n`個の区別できる玉を`k`個の区別できる箱に、空箱を作らずに入れる方法の数
+    ///
+    /// 包除原理で `sum (-1)^i kCi (k - i)^n` (`= k! S(n, k)`)
+    ///
+    /// `O(k log n)`、modは`k`より大きい素数であること
+    pub fn surjections(n: usize, k: usize, modulus: u32) -> ModInt {
+        let zero = ModInt::new(0, modulus);
+        if k > n {
+            return zero;
+        }
+        let tbl = BCTDP::new(k, modulus as usize);
+        (0..=k).fold(zero, |acc, i| {
+            let term = tbl.binomial(k, i) * ModInt::new(k - i, modulus).pow(n);
+            if i % 2 == 0 {
+                acc + term
+            } else {
+                acc - term
+            }
+        })
+    }
+
+    /// `x_1 + ... + x_k = n` の解の個数 (`(非負整数解, 正整数解)`)
+    ///
+    /// 非負は `(n + k - 1) C (k - 1)`、正は `(n - 1) C (k - 1)`
+    ///
+    /// `k == 0`ならどちらも`n == 0`の時だけ1
+    ///
+    /// `O(n + k)`、modは`n + k`より大きい素数であること
+    pub fn stars_and_bars(n: usize, k: usize, modulus: u32) -> (ModInt, ModInt) {
+        let zero = ModInt::new(0, modulus);
+        if k == 0 {
+            let x = ModInt::from_bool(n == 0, modulus);
+            return (x, x);
+        }
+        let tbl = BCTDP::new(n + k, modulus as usize);
+        let nonnegative = tbl.binomial(n + k - 1, k - 1);
+        let positive = if n >= k {
+            tbl.binomial(n - 1, k - 1)
+        } else {
+            zero
+        };
+        (zero + nonnegative, positive)
+    }
+
+    /// `(0, 0)`から`(dx, dy)`へ右か上に1ずつ進む経路の数 `C(dx + dy, dx)`
+    ///
+    /// `O(dx + dy)`、modは`dx + dy`より大きい素数であること
+    pub fn lattice_paths(dx: usize, dy: usize, modulus: u32) -> ModInt {
+        let tbl = BCTDP::new(dx + dy, modulus as usize);
+        ModInt::new(0, modulus) + tbl.binomial(dx + dy, dx)
+    }
+
+    /// `lattice_paths`のうち、対角線`y = x`より上に出ない(常に`y <= x`)経路の数
+    ///
+    /// 鏡像の原理で、出てしまう経路は`(dy - 1, dx + 1)`への経路と一対一なので
+    /// `C(dx + dy, dx) - C(dx + dy, dx + 1)`、`dx == dy`ならカタラン数
+    ///
+    /// `dy > dx`なら0、`O(dx + dy)`、modは`dx + dy`より大きい素数であること
+    pub fn catalan_paths(dx: usize, dy: usize, modulus: u32) -> ModInt {
+        let zero = ModInt::new(0, modulus);
+        if dy > dx {
+            return zero;
+        }
+        let tbl = BCTDP::new(dx + dy, modulus as usize);
+        zero + tbl.binomial(dx + dy, dx) - tbl.binomial(dx + dy, dx + 1)
+    }
+}
+
+#[test]
+fn surjections_test() {
+    let m = 1_000_000_007;
+    assert_eq!(surjections(3, 2, m).get(), 6);
+    assert_eq!(surjections(4, 2, m).get(), 14);
+    // k! S(n, k)、S(5, 3) = 25
+    assert_eq!(surjections(5, 3, m).get(), 150);
+    // n == k なら順列
+    assert_eq!(surjections(6, 6, m).get(), 720);
+    assert_eq!(surjections(2, 3, m).get(), 0);
+    assert_eq!(surjections(0, 0, m).get(), 1);
+    assert_eq!(surjections(3, 0, m).get(), 0);
+}
+
+#[test]
+fn stars_and_bars_test() {
+    let m = 1_000_000_007;
+    // x + y + z = 5
+    let (nonneg, pos) = stars_and_bars(5, 3, m);
+    assert_eq!(nonneg.get(), 21);
+    assert_eq!(pos.get(), 6);
+    assert_eq!(nonneg.get_mod(), m as usize);
+
+    let (nonneg, pos) = stars_and_bars(2, 3, m);
+    assert_eq!((nonneg.get(), pos.get()), (6, 0));
+    let (nonneg, pos) = stars_and_bars(0, 1, m);
+    assert_eq!((nonneg.get(), pos.get()), (1, 0));
+    let (nonneg, pos) = stars_and_bars(0, 0, m);
+    assert_eq!((nonneg.get(), pos.get()), (1, 1));
+    let (nonneg, pos) = stars_and_bars(3, 0, m);
+    assert_eq!((nonneg.get(), pos.get()), (0, 0));
+}
+
+#[test]
+fn lattice_paths_test() {
+    let m = 1_000_000_007;
+    assert_eq!(lattice_paths(2, 2, m).get(), 6);
+    assert_eq!(lattice_paths(3, 0, m).get(), 1);
+    assert_eq!(lattice_paths(0, 0, m).get(), 1);
+    assert_eq!(lattice_paths(10, 10, m).get(), 184_756);
+
+    // カタラン数
+    let catalan = [1, 1, 2, 5, 14, 42, 132, 429];
+    for (n, &c) in catalan.iter().enumerate() {
+        assert_eq!(catalan_paths(n, n, m).get(), c);
+    }
+    assert_eq!(catalan_paths(2, 3, m).get(), 0);
+    // 投票の問題 (3, 1): 全4通りのうち y <= x を保つのは3通り
+    assert_eq!(catalan_paths(3, 1, m).get(), 3);
+
+    // 小さい格子でDPと比較
+    for dx in 0..6usize {
+        for dy in 0..6usize {
+            let mut dp = vec![vec![0i64; dy + 1]; dx + 1];
+            dp[0][0] = 1;
+            for x in 0..=dx {
+                for y in 0..=dy {
+                    if y > x {
+                        dp[x][y] = 0;
+                        continue;
+                    }
+                    if x > 0 {
+                        dp[x][y] += dp[x - 1][y];
+                    }
+                    if y > 0 {
+                        dp[x][y] += dp[x][y - 1];
+                    }
+                }
+            }
+            assert_eq!(catalan_paths(dx, dy, m).get(), dp[dx][dy]);
+        }
+    }
+}
@@ -1,12 +1,14 @@
 pub use factorial::*;
 
 pub mod factorial {
-    use num_traits::{NumAssignOps, NumOps, One};
+    use num_traits::{NumAssignOps, NumOps, One, Zero};
     use std::convert::TryInto;
 
     pub trait Factoriable: Sized + NumOps + NumAssignOps + Copy + TryInto<usize> {
         fn falling(self, take: usize) -> Self;
         fn rising(self, take: usize) -> Self;
+        /// `self`と同じ種類の1、`ModInt`ならmodを引き継ぐ
+        fn one_like(self) -> Self;
         fn factorial(self) -> Self {
             self.falling(self.try_into().ok().unwrap())
         }
@@ -34,12 +36,103 @@ pub mod factorial {
                     }
                     res
                 }
+                fn one_like(self) -> Self {
+                    Self::one()
+                }
             }
         )*
     };
 }
 
     impl_factorialbe!(usize, u8, u16, u32, u64, isize, i8, i16, i32, i64);
+
+    /// `x`の下降階乗 `1, x, x(x-1), x(x-1)(x-2), ...` を順に返す無限Iterator
+    ///
+    /// `k`番目の値は`x.falling(k)`に等しく、1項進めるごとに乗算1回
+    ///
+    /// 一度0を掛けたら以降は0のまま(符号なし整数でも溢れない)
+    #[derive(Debug, Clone)]
+    pub struct FallingFactorial<T> {
+        acc: T,
+        next: T,
+    }
+
+    impl<T: Factoriable + One + Zero> FallingFactorial<T> {
+        pub fn new(x: T) -> Self {
+            Self {
+                acc: x.one_like(),
+                next: x,
+            }
+        }
+    }
+
+    impl<T: Factoriable + One + Zero> Iterator for FallingFactorial<T> {
+        type Item = T;
+        fn next(&mut self) -> Option<T> {
+            let res = self.acc;
+            self.acc *= self.next;
+            if !self.next.is_zero() {
+                self.next -= T::one();
+            }
+            Some(res)
+        }
+    }
+
+    /// `x`の上昇階乗 `1, x, x(x+1), x(x+1)(x+2), ...` を順に返す無限Iterator
+    ///
+    /// `k`番目の値は`x.rising(k)`に等しい
+    #[derive(Debug, Clone)]
+    pub struct RisingFactorial<T> {
+        acc: T,
+        next: T,
+    }
+
+    impl<T: Factoriable + One> RisingFactorial<T> {
+        pub fn new(x: T) -> Self {
+            Self {
+                acc: x.one_like(),
+                next: x,
+            }
+        }
+    }
+
+    impl<T: Factoriable + One> Iterator for RisingFactorial<T> {
+        type Item = T;
+        fn next(&mut self) -> Option<T> {
+            let res = self.acc;
+            self.acc *= self.next;
+            self.next += T::one();
+            Some(res)
+        }
+    }
+
+    #[test]
+    fn falling_factorial_iter_test() {
+        use crate::modint::ModInt;
+
+        let x = ModInt::new(100, 1_000_000_007);
+        // 0番目の1もxのmodを持つ
+        assert_eq!(
+            FallingFactorial::new(x).next().unwrap().get_mod(),
+            1_000_000_007
+        );
+        assert_eq!(
+            RisingFactorial::new(x).next().unwrap().get_mod(),
+            1_000_000_007
+        );
+        for (k, f) in FallingFactorial::new(x).take(120).enumerate() {
+            assert_eq!(f.get(), x.falling(k).get());
+            assert_eq!(f.get_mod(), 1_000_000_007);
+        }
+        for (k, r) in RisingFactorial::new(x).take(50).enumerate() {
+            assert_eq!(r.get(), x.rising(k).get());
+        }
+
+        let v = FallingFactorial::new(5usize).take(8).collect::<Vec<_>>();
+        assert_eq!(v, vec![1, 5, 20, 60, 120, 120, 0, 0]);
+        let v = RisingFactorial::new(3u64).take(4).collect::<Vec<_>>();
+        assert_eq!(v, vec![1, 3, 12, 60]);
+    }
 }
 #[test]
 fn fact_test_prim() {
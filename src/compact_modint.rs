@@ -0,0 +1,165 @@
+use crate::modint::ModInt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+/// 剰余とmodをそれぞれ`u32`で持つ`ModInt`
+///
+/// `ModInt`の半分の大きさなので、巨大な配列でDPするときにメモリ帯域を節約できる
+///
+/// 演算の途中は`u64`で計算する
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CompactModInt {
+    num: u32,
+    modulus: u32,
+}
+
+impl CompactModInt {
+    pub fn new(n: u64, modulus: u32) -> Self {
+        assert!(modulus != 0, "modulus must be positive");
+        Self {
+            num: (n % modulus as u64) as u32,
+            modulus,
+        }
+    }
+
+    pub fn get(self) -> u32 {
+        self.num
+    }
+
+    pub fn modulus(self) -> u32 {
+        self.modulus
+    }
+
+    fn check(self, rhs: Self) -> u64 {
+        assert_eq!(
+            self.modulus, rhs.modulus,
+            "modulus mismatch: lhs mod {} vs rhs mod {}",
+            self.modulus, rhs.modulus
+        );
+        self.modulus as u64
+    }
+}
+
+/// `a, b <= m`
+fn add_mod(a: u64, b: u64, m: u64) -> u32 {
+    let r = a + b;
+    (if r >= m { r - m } else { r }) as u32
+}
+
+/// # Panic
+/// if variant is Modulo::Dynamic
+impl From<ModInt> for CompactModInt {
+    fn from(x: ModInt) -> Self {
+        Self {
+            num: x.get() as u32,
+            modulus: x.get_mod() as u32,
+        }
+    }
+}
+
+impl From<CompactModInt> for ModInt {
+    fn from(x: CompactModInt) -> Self {
+        ModInt::new(x.num, x.modulus)
+    }
+}
+
+impl Add for CompactModInt {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        let m = self.check(rhs);
+        Self {
+            num: add_mod(self.num as u64, rhs.num as u64, m),
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl Sub for CompactModInt {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        let m = self.check(rhs);
+        Self {
+            num: add_mod(self.num as u64, m - rhs.num as u64, m),
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl Mul for CompactModInt {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let m = self.check(rhs);
+        Self {
+            num: (self.num as u64 * rhs.num as u64 % m) as u32,
+            modulus: self.modulus,
+        }
+    }
+}
+
+/// # Panic
+/// `rhs`の逆元がない時
+impl Div for CompactModInt {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        self.check(rhs);
+        let inv = ModInt::from(rhs)
+            .checked_inv()
+            .expect("divisor is not invertible");
+        self.mul(Self::from(inv))
+    }
+}
+
+impl AddAssign for CompactModInt {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for CompactModInt {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for CompactModInt {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for CompactModInt {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+#[test]
+fn compact_size_test() {
+    use std::mem::size_of;
+    assert!(size_of::<CompactModInt>() < size_of::<ModInt>());
+    assert_eq!(size_of::<CompactModInt>(), 8);
+}
+
+#[test]
+fn compact_arith_test() {
+    for &m in &[1_000_000_007u32, 998_244_353, 4_294_967_291, 12] {
+        let xs = [0u64, 1, 2, 12_345, 999_999_999, 4_294_967_290, 1 << 40];
+        for &a in &xs {
+            for &b in &xs {
+                let (ca, cb) = (CompactModInt::new(a, m), CompactModInt::new(b, m));
+                let (ma, mb) = (ModInt::new(a, m), ModInt::new(b, m));
+                assert_eq!(ModInt::from(ca + cb), ma + mb);
+                assert_eq!(ModInt::from(ca - cb), ma - mb);
+                assert_eq!(ModInt::from(ca * cb), ma * mb);
+                if mb.checked_inv().is_some() {
+                    assert_eq!(ModInt::from(ca / cb), ma / mb);
+                }
+            }
+        }
+    }
+    let x = ModInt::new(-1, 7);
+    assert_eq!(ModInt::from(CompactModInt::from(x)), x);
+    let mut c = CompactModInt::new(3, 7);
+    c *= CompactModInt::new(5, 7);
+    c -= CompactModInt::new(1, 7);
+    assert_eq!(c.get(), 0);
+}
@@ -0,0 +1,133 @@
+use crate::modint::ModInt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+/// 同じ値を2つの異なるmodで持ち、全ての演算を両方で行うデバッグ用の`ModInt`
+///
+/// どちらか一方のmodでだけ一致するような比較が起きたら、
+/// オーバーフローや実装のミスで2つの値がずれたとみなしてpanicする
+///
+/// 提出用ではなく、WAの原因探しに
+#[derive(Debug, Clone, Copy)]
+pub struct DualModInt {
+    primary: ModInt,
+    check: ModInt,
+}
+
+impl DualModInt {
+    /// `primary_mod`が本来のmod、`check_mod`が検査用のmod
+    pub fn new(n: i64, primary_mod: u32, check_mod: u32) -> Self {
+        assert_ne!(primary_mod, check_mod, "moduli must be different");
+        Self {
+            primary: ModInt::new(n, primary_mod),
+            check: ModInt::new(n, check_mod),
+        }
+    }
+
+    /// 本来のmodでの値
+    pub fn get(&self) -> ModInt {
+        self.primary
+    }
+
+    /// 検査用のmodでの値
+    pub fn get_check(&self) -> ModInt {
+        self.check
+    }
+}
+
+/// # Panic
+/// 片方のmodでは等しく、もう片方では異なる時
+impl PartialEq for DualModInt {
+    fn eq(&self, other: &Self) -> bool {
+        let p = self.primary == other.primary;
+        let c = self.check == other.check;
+        if p != c {
+            panic!(
+                "DualModInt diverged: primary {} vs {}, check {} vs {}",
+                self.primary.get(),
+                other.primary.get(),
+                self.check.get(),
+                other.check.get()
+            );
+        }
+        p
+    }
+}
+
+macro_rules! impl_dual_ops {
+    ($($tr:ident, $f:ident, $tra:ident, $fa:ident);*) => {
+        $(
+            impl $tr for DualModInt {
+                type Output = Self;
+                fn $f(self, rhs: Self) -> Self::Output {
+                    Self {
+                        primary: self.primary.$f(rhs.primary),
+                        check: self.check.$f(rhs.check),
+                    }
+                }
+            }
+
+            impl $tr<i64> for DualModInt {
+                type Output = Self;
+                fn $f(self, rhs: i64) -> Self::Output {
+                    Self {
+                        primary: self.primary.$f(rhs),
+                        check: self.check.$f(rhs),
+                    }
+                }
+            }
+
+            impl $tra for DualModInt {
+                fn $fa(&mut self, rhs: Self) {
+                    *self = self.$f(rhs);
+                }
+            }
+
+            impl $tra<i64> for DualModInt {
+                fn $fa(&mut self, rhs: i64) {
+                    *self = self.$f(rhs);
+                }
+            }
+        )*
+    };
+}
+
+impl_dual_ops!(
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign;
+    Div, div, DivAssign, div_assign
+);
+
+#[test]
+fn dual_modint_agree_test() {
+    let (p, q) = (1_000_000_007, 998_244_353);
+    let mut x = DualModInt::new(1, p, q);
+    for i in 1..=20 {
+        x *= i;
+    }
+    // 20! / 18! = 380
+    let y = x / DualModInt::new(1, p, q) / 19 / 20;
+    let mut fact18 = DualModInt::new(1, p, q);
+    for i in 1..=18 {
+        fact18 *= i;
+    }
+    assert_eq!(y, fact18);
+    assert_eq!(x / fact18, DualModInt::new(380, p, q));
+    assert_ne!(x, fact18);
+    assert_eq!(x.get(), ModInt::new(146_326_063, p));
+}
+
+#[test]
+fn dual_modint_corruption_test() {
+    let (p, q) = (1_000_000_007, 998_244_353);
+    let a = DualModInt::new(12345, p, q);
+    let b = DualModInt::new(67890, p, q);
+    let expected = DualModInt::new(12345 * 67890, p, q);
+    assert_eq!(a * b, expected);
+
+    // 片方のmodでだけ計算を壊す
+    let mut broken = a * b;
+    broken.check += 1;
+    let res = std::panic::catch_unwind(|| broken == expected);
+    assert!(res.is_err());
+}
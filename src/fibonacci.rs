@@ -0,0 +1,75 @@
+use crate::modint::ModInt;
+
+/// `(F(n), F(n + 1))` を fast doubling で
+fn fib_pair(n: u64, modulus: u32) -> (ModInt, ModInt) {
+    if n == 0 {
+        return (ModInt::new(0, modulus), ModInt::new(1, modulus));
+    }
+    let (f0, f1) = fib_pair(n / 2, modulus);
+    // F(2k) = F(k) * (2F(k + 1) - F(k))
+    // F(2k + 1) = F(k)^2 + F(k + 1)^2
+    let even = f0 * (f1 * 2 - f0);
+    let odd = f0 * f0 + f1 * f1;
+    if n % 2 == 0 {
+        (even, odd)
+    } else {
+        (odd, even + odd)
+    }
+}
+
+/// フィボナッチ数 `F(n) mod m`
+///
+/// `F(0) = 0, F(1) = 1`
+///
+/// fast doubling で `O(log n)`
+pub fn fib_mod(n: u64, modulus: u32) -> ModInt {
+    fib_pair(n, modulus).0
+}
+
+/// リュカ数 `L(n) mod m`
+///
+/// `L(0) = 2, L(1) = 1`, `L(n) = 2F(n + 1) - F(n)`
+///
+/// `O(log n)`
+pub fn lucas_mod(n: u64, modulus: u32) -> ModInt {
+    let (a, b) = fib_pair(n, modulus);
+    b * 2 - a
+}
+
+#[test]
+fn fib_test() {
+    let fib = [0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89, 144, 233, 377];
+    for (i, &f) in fib.iter().enumerate() {
+        assert_eq!(fib_mod(i as u64, 1_000_000_007).get(), f);
+    }
+    assert_eq!(fib_mod(14, 100).get(), 77);
+
+    let lucas = [2, 1, 3, 4, 7, 11, 18, 29, 47, 76, 123, 199, 322, 521, 843];
+    for (i, &l) in lucas.iter().enumerate() {
+        assert_eq!(lucas_mod(i as u64, 1_000_000_007).get(), l);
+    }
+}
+
+#[test]
+fn fib_large_test() {
+    // 行列累乗との比較
+    fn mat_mul(lhs: [[u64; 2]; 2], rhs: [[u64; 2]; 2], m: u64) -> [[u64; 2]; 2] {
+        let cell = |i: usize, j: usize| (lhs[i][0] * rhs[0][j] + lhs[i][1] * rhs[1][j]) % m;
+        [[cell(0, 0), cell(0, 1)], [cell(1, 0), cell(1, 1)]]
+    }
+    let m = 1_000_000_007;
+    let mut n = 1_000_000_000_000_000_000u64;
+    let mut res = [[1, 0], [0, 1]];
+    let mut base = [[1, 1], [1, 0]];
+    while n > 0 {
+        if n & 1 == 1 {
+            res = mat_mul(res, base, m);
+        }
+        base = mat_mul(base, base, m);
+        n >>= 1;
+    }
+    assert_eq!(
+        fib_mod(1_000_000_000_000_000_000, m as u32).get(),
+        res[0][1] as i64
+    );
+}
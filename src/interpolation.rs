@@ -0,0 +1,259 @@
+use crate::combinatorics::binomial_coefficient::{BinomialCoefficient, BCTDP};
+use crate::matrix::Matrix;
+use crate::modint::ModInt;
+use num_traits::Pow;
+
+/// `x = 0, 1, ..., d` での値`samples`から、`d`次以下の多項式の`x`での値をラグランジュ補間で求める
+///
+/// `O(d)`、modは`d`より大きい素数であること
+pub fn interpolate_consecutive(samples: &[ModInt], x: u64) -> ModInt {
+    let d = samples.len() - 1;
+    if x <= d as u64 {
+        return samples[x as usize];
+    }
+    let m = samples[0].get_mod();
+    let x = ModInt::new(x % m as u64, m);
+
+    // prefix[i] = (x - 0)(x - 1)...(x - (i - 1))
+    let mut prefix = vec![ModInt::new(1, m); d + 2];
+    for i in 0..=d {
+        prefix[i + 1] = prefix[i] * (x - i);
+    }
+    // suffix[i] = (x - i)(x - (i + 1))...(x - d)
+    let mut suffix = vec![ModInt::new(1, m); d + 2];
+    for i in (0..=d).rev() {
+        suffix[i] = suffix[i + 1] * (x - i);
+    }
+
+    let tbl = BCTDP::new(d, m);
+    let mut res = ModInt::new(0, m);
+    for (i, &y) in samples.iter().enumerate() {
+        // 分母は i! * (d - i)! * (-1)^(d - i)
+        let t =
+            y * prefix[i] * suffix[i + 1] * tbl.factorial_inverse(i) * tbl.factorial_inverse(d - i);
+        if (d - i) % 2 == 0 {
+            res += t;
+        } else {
+            res -= t;
+        }
+    }
+    res
+}
+
+/// `d`次以下の多項式`f`について、累積和`f(0) + f(1) + ... + f(n)`を求める
+///
+/// `samples`は`f`そのものの値`f(0), f(1), ..., f(d + 1)`(累積和ではない)、modも`samples`から決める
+///
+/// 累積和は`d + 1`次なので、`d + 2`点の累積和をラグランジュ補間して`O(d)`
+///
+/// modは`d + 1`より大きい素数であること
+///
+/// # Panic
+/// `samples`が空の時
+pub fn prefix_polynomial_sum(samples: &[ModInt], n: u64) -> ModInt {
+    assert!(!samples.is_empty(), "samples must not be empty");
+    let mut acc = samples[0];
+    let mut prefix = Vec::with_capacity(samples.len());
+    prefix.push(acc);
+    for &y in &samples[1..] {
+        acc += y;
+        prefix.push(acc);
+    }
+    interpolate_consecutive(&prefix, n)
+}
+
+/// `1^k + 2^k + ... + n^k mod m`
+///
+/// 和は`n`について`k + 1`次の多項式なので、`k + 2`点でラグランジュ補間して`O(k log k)`
+///
+/// modは`k + 1`より大きい素数であること
+pub fn power_sum(n: u64, k: u32, modulus: u32) -> ModInt {
+    if k == 0 {
+        return ModInt::new(n % modulus as u64, modulus);
+    }
+    let samples = (0..=k as usize + 1)
+        .map(|i| ModInt::new(i, modulus).pow(k as usize))
+        .collect::<Vec<_>>();
+    prefix_polynomial_sum(&samples, n)
+}
+
+/// ベルヌーイ数 `B_0, B_1, ..., B_n`
+///
+/// `sum_{k=0}^{j} C(j + 1, k) B_k = 0` (`j >= 1`) から順に求めるので`B_1 = -1/2`の流儀
+/// (`B_1 = +1/2`が欲しい時は符号を反転する)
+///
+/// `O(n^2)`、modは`n + 1`より大きい素数であること
+pub fn bernoulli_numbers(n: usize, modulus: u32) -> Vec<ModInt> {
+    let tbl = BCTDP::new(n + 1, modulus as usize);
+    let mut b = Vec::with_capacity(n + 1);
+    b.push(ModInt::new(1, modulus));
+    for j in 1..=n {
+        let s = (0..j).fold(ModInt::new(0, modulus), |acc, k| {
+            acc + tbl.binomial(j + 1, k) * b[k]
+        });
+        b.push(ModInt::new(0, modulus) - s * tbl.inv(j + 1));
+    }
+    b
+}
+
+/// ヴァンデルモンド行列`V[i][j] = nodes[i]^j`の逆行列
+///
+/// `V^(-1) * y`が点`(nodes[i], y[i])`を通す多項式の係数になるので、
+/// 同じ点で何度も補間するときに一度だけ作っておく
+///
+/// `j`列目はラグランジュ基底`L_j(x)`の係数なので`O(n^2)`
+///
+/// modは素数であること
+///
+/// # Panic
+/// `nodes`が空の時、同じ点を含む時
+pub fn vandermonde_inverse(nodes: &[ModInt]) -> Matrix {
+    assert!(!nodes.is_empty(), "nodes must not be empty");
+    let n = nodes.len();
+    let m = nodes[0].get_mod();
+    let zero = ModInt::new(0, m);
+
+    // master = prod (x - nodes[j])
+    let mut master = vec![zero; n + 1];
+    master[0] = ModInt::new(1, m);
+    for (deg, &x) in nodes.iter().enumerate() {
+        for i in (0..=deg).rev() {
+            let c = master[i];
+            master[i + 1] += c;
+            master[i] = zero - c * x;
+        }
+    }
+
+    let mut res = Matrix::new(n, n, m as u32);
+    for (j, &x) in nodes.iter().enumerate() {
+        // master / (x - nodes[j]) を組立除法で
+        let mut basis = vec![zero; n];
+        let mut carry = master[n];
+        for i in (0..n).rev() {
+            basis[i] = carry;
+            carry = master[i] + carry * x;
+        }
+        let denom = basis.iter().rev().fold(zero, |acc, &c| acc * x + c);
+        let inv = denom
+            .checked_inv()
+            .expect("nodes must be pairwise distinct");
+        for (i, &c) in basis.iter().enumerate() {
+            res[(i, j)] = c * inv;
+        }
+    }
+    res
+}
+
+#[test]
+fn interpolate_test() {
+    // f(x) = x^2 + 1
+    let m = 1_000_000_007;
+    let samples = (0..3)
+        .map(|x| ModInt::new(x * x + 1, m))
+        .collect::<Vec<_>>();
+    assert_eq!(interpolate_consecutive(&samples, 1).get(), 2);
+    assert_eq!(interpolate_consecutive(&samples, 10).get(), 101);
+    assert_eq!(interpolate_consecutive(&samples, 1000).get(), 1_000_001);
+}
+
+#[test]
+fn power_sum_test() {
+    let m = 1_000_000_007;
+    assert_eq!(power_sum(10, 2, m).get(), 385);
+    assert_eq!(power_sum(10, 1, m).get(), 55);
+    assert_eq!(power_sum(10, 0, m).get(), 10);
+    assert_eq!(power_sum(0, 3, m).get(), 0);
+
+    // n(n + 1)(2n + 1) / 6
+    let n = 1_000_000_000_000u64;
+    let expected = (n as u128 * (n as u128 + 1) * (2 * n as u128 + 1) / 6) % m as u128;
+    assert_eq!(power_sum(n, 2, m).get(), expected as i64);
+
+    // 素朴な和と比較
+    let naive = (1..=20usize).fold(ModInt::new(0, 13), |acc, i| acc + ModInt::new(i, 13).pow(5));
+    assert_eq!(power_sum(20, 5, 13), naive);
+}
+
+#[test]
+fn prefix_polynomial_sum_test() {
+    let m = 1_000_000_007;
+    // sum i^3 = (n(n + 1) / 2)^2
+    let cubes = (0..5)
+        .map(|i| ModInt::new(i * i * i, m))
+        .collect::<Vec<_>>();
+    for &n in &[0u64, 1, 4, 10, 1_000_000, 1_000_000_000_000_000] {
+        let t = (n as u128 * (n as u128 + 1) / 2 % m as u128) as i64;
+        let t = ModInt::new(t, m);
+        assert_eq!(prefix_polynomial_sum(&cubes, n), t * t);
+    }
+
+    // f(i) = 2i^2 - 3i + 5、余分な点があってもよい
+    let f = |i: i64| ModInt::new(2 * i * i - 3 * i + 5, m);
+    let samples = (0..6).map(f).collect::<Vec<_>>();
+    let naive = (0..=100).fold(ModInt::new(0, m), |acc, i| acc + f(i));
+    assert_eq!(prefix_polynomial_sum(&samples, 100), naive);
+    assert_eq!(prefix_polynomial_sum(&samples[..4], 100), naive);
+    assert_eq!(prefix_polynomial_sum(&samples, 2), f(0) + f(1) + f(2));
+    assert_eq!(prefix_polynomial_sum(&samples, 0), f(0));
+    assert_eq!(prefix_polynomial_sum(&samples, 0).get_mod(), m as usize);
+}
+
+#[test]
+fn bernoulli_numbers_test() {
+    let m = 1_000_000_007;
+    let frac = |p: i64, q: i64| ModInt::from_fraction(p, q, m);
+    let b = bernoulli_numbers(10, m);
+    assert_eq!(b.len(), 11);
+    assert_eq!(b[0], frac(1, 1));
+    assert_eq!(b[1], frac(-1, 2));
+    assert_eq!(b[2], frac(1, 6));
+    assert_eq!(b[4], frac(-1, 30));
+    assert_eq!(b[6], frac(1, 42));
+    assert_eq!(b[8], frac(-1, 30));
+    assert_eq!(b[10], frac(5, 66));
+    for k in 1..5 {
+        assert_eq!(b[2 * k + 1].get(), 0);
+    }
+    assert_eq!(bernoulli_numbers(0, m), vec![ModInt::new(1, m)]);
+
+    // ファウルハーバーの公式 sum_{i=0}^{n-1} i^k = 1/(k+1) sum_j C(k+1, j) B_j n^(k+1-j)
+    let (n, k) = (1000u64, 4usize);
+    let tbl = BCTDP::new(k + 1, m as usize);
+    let nm = ModInt::new(n as i64, m);
+    let faulhaber = (0..=k).fold(ModInt::new(0, m), |acc, j| {
+        acc + tbl.binomial(k + 1, j) * b[j] * nm.pow(k + 1 - j)
+    }) * tbl.inv(k + 1);
+    assert_eq!(faulhaber, power_sum(n - 1, k as u32, m));
+}
+
+#[test]
+fn vandermonde_inverse_test() {
+    let m = 1_000_000_007;
+    let nodes = [1, 2, 4]
+        .iter()
+        .map(|&x| ModInt::new(x, m))
+        .collect::<Vec<_>>();
+    let inv = vandermonde_inverse(&nodes);
+
+    let mut v = Matrix::new(3, 3, m);
+    for i in 0..3 {
+        for j in 0..3 {
+            v[(i, j)] = nodes[i].pow(j);
+        }
+    }
+    assert_eq!(&inv * &v, Matrix::identity(3, m));
+
+    let interpolate = |values: &[i64]| {
+        let mut coef = vec![ModInt::new(0, m); 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                coef[i] += inv[(i, j)] * values[j];
+            }
+        }
+        coef.iter().map(|c| c.get()).collect::<Vec<_>>()
+    };
+    // f(x) = x^2 + 1
+    assert_eq!(interpolate(&[2, 5, 17]), vec![1, 0, 1]);
+    // f(x) = 3 - 2x
+    assert_eq!(interpolate(&[1, -1, -5]), vec![3, m as i64 - 2, 0]);
+}
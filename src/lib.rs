@@ -1,18 +1,37 @@
+pub mod barrett;
 pub mod binary_powering;
 pub mod binary_search;
 pub mod bitset;
 pub mod combinatorics;
+pub mod compact_modint;
+pub mod dual_modint;
+pub mod fibonacci;
 pub mod graph;
+pub mod interpolation;
 pub mod inversation;
+pub mod linear_recurrence;
+pub mod matrix;
 pub mod modint;
+pub mod ntt;
+pub mod polynomial;
 pub mod prime;
+pub mod probability;
+#[cfg(all(test, feature = "rand"))]
+pub mod ring_laws;
+pub mod rolling_hash;
 pub mod run_length_encoding;
+#[cfg(feature = "rand")]
 pub mod shuffle;
+#[cfg(test)]
+pub mod slow_modint;
 pub mod structure;
 pub mod unique_count;
 
 #[macro_use]
 pub mod flex_maxmin;
 
+#[macro_use]
+pub mod mint;
+
 #[macro_use]
 pub mod visualize;
@@ -0,0 +1,76 @@
+use crate::modint::ModInt;
+
+/// 列`seq`を生成する最短の線形漸化式 `seq[i] = c[0] seq[i - 1] + ... + c[d - 1] seq[i - d]` の係数`c`
+///
+/// Berlekamp–Massey法で`O(n^2)`、modは素数であること
+///
+/// 長さ`2d`以上の接頭辞があれば`d`項間漸化式を復元できる
+pub fn berlekamp_massey(seq: &[ModInt]) -> Vec<ModInt> {
+    let m = match seq.first() {
+        Some(x) => x.get_mod(),
+        None => return Vec::new(),
+    };
+    let zero = ModInt::new(0, m);
+    // c(x) = 1 - c[0]x - c[1]x^2 - ... を係数の列で持つ
+    let mut cur = vec![ModInt::new(1, m)];
+    let mut prev = vec![ModInt::new(1, m)];
+    let mut len = 0;
+    let mut shift = 1;
+    let mut prev_disc = ModInt::new(1, m);
+    for n in 0..seq.len() {
+        let disc = cur
+            .iter()
+            .take(len + 1)
+            .enumerate()
+            .fold(zero, |acc, (i, &c)| acc + c * seq[n - i]);
+        if disc.get() == 0 {
+            shift += 1;
+            continue;
+        }
+        let coef = disc * prev_disc.checked_inv().expect("modulus must be prime");
+        let before = cur.clone();
+        if cur.len() < prev.len() + shift {
+            cur.resize(prev.len() + shift, zero);
+        }
+        for (i, &b) in prev.iter().enumerate() {
+            cur[i + shift] -= coef * b;
+        }
+        if 2 * len <= n {
+            len = n + 1 - len;
+            prev = before;
+            prev_disc = disc;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+    }
+    cur.resize(len + 1, zero);
+    cur[1..].iter().map(|&x| zero - x).collect()
+}
+
+#[test]
+fn berlekamp_massey_test() {
+    let m = 1_000_000_007;
+    let to_mint = |v: &[i64]| v.iter().map(|&x| ModInt::new(x, m)).collect::<Vec<_>>();
+
+    // フィボナッチ
+    let fib = to_mint(&[0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+    assert_eq!(berlekamp_massey(&fib), to_mint(&[1, 1]));
+
+    // a[i] = 2a[i-1] - a[i-2] + 3a[i-3] を長めに生成して比較
+    let c = to_mint(&[2, -1, 3]);
+    let mut seq = to_mint(&[5, -7, 11]);
+    for i in 3..20 {
+        let next = (0..3).fold(ModInt::new(0, m), |acc, j| acc + c[j] * seq[i - 1 - j]);
+        seq.push(next);
+    }
+    assert_eq!(berlekamp_massey(&seq), c);
+    assert_eq!(berlekamp_massey(&seq[..6]), c);
+
+    // 等比数列は1項間
+    let geo = ModInt::new(3, m).powers().take(8).collect::<Vec<_>>();
+    assert_eq!(berlekamp_massey(&geo), to_mint(&[3]));
+    // 0だけなら漸化式は空
+    assert!(berlekamp_massey(&to_mint(&[0, 0, 0])).is_empty());
+    assert!(berlekamp_massey(&[]).is_empty());
+}
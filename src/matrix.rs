@@ -0,0 +1,563 @@
+use crate::modint::{axpy, dot, modulo_mismatch, ModInt};
+use std::ops::{Index, IndexMut, Mul};
+
+/// `mod m`上の行列
+///
+/// 全要素が同じmodを共有する
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    modulus: u32,
+    rows: usize,
+    cols: usize,
+    data: Vec<Vec<ModInt>>,
+}
+
+impl Matrix {
+    /// `rows x cols`の零行列
+    pub fn new(rows: usize, cols: usize, modulus: u32) -> Self {
+        Self {
+            modulus,
+            rows,
+            cols,
+            data: vec![vec![ModInt::new(0, modulus); cols]; rows],
+        }
+    }
+
+    /// 行のリストから作る
+    ///
+    /// # Panic
+    /// 空の時、行の長さが揃っていない時、`Static`なmodが揃っていない時
+    pub fn from_rows(rows: Vec<Vec<ModInt>>) -> Self {
+        let cols = rows.first().map_or(0, |r| r.len());
+        assert!(cols > 0, "matrix must not be empty");
+        assert!(
+            rows.iter().all(|r| r.len() == cols),
+            "rows must have the same length"
+        );
+        let modulus = rows[0][0].try_get_mod().expect("modulo must be static") as u32;
+        for x in rows.iter().flatten() {
+            if x.try_get_mod() != Some(modulus as usize) {
+                modulo_mismatch(&rows[0][0], x)
+            }
+        }
+        Self {
+            modulus,
+            rows: rows.len(),
+            cols,
+            data: rows,
+        }
+    }
+
+    /// `n x n`の単位行列
+    pub fn identity(n: usize, modulus: u32) -> Self {
+        let mut res = Self::new(n, n, modulus);
+        for i in 0..n {
+            res[(i, i)] = ModInt::new(1, modulus);
+        }
+        res
+    }
+
+    pub fn modulus(&self) -> u32 {
+        self.modulus
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    /// `i`行目
+    pub fn row(&self, i: usize) -> &[ModInt] {
+        &self.data[i]
+    }
+
+    /// 行列とベクトルの積 `M v`
+    ///
+    /// 各行との内積を`dot`でとるので、積は`u128`に溜めて行ごとに一度だけ剰余をとる
+    ///
+    /// # Panic
+    /// `v`の長さが列数と異なる時、`v`のmodが異なる時
+    pub fn mul_vec(&self, v: &[ModInt]) -> Vec<ModInt> {
+        assert_eq!(self.cols, v.len(), "dimension mismatch");
+        let zero = ModInt::new(0, self.modulus);
+        self.data.iter().map(|row| zero + dot(row, v)).collect()
+    }
+
+    /// 行列式
+    ///
+    /// ガウスの消去法で`O(n^3)`、modは素数であること
+    ///
+    /// # Panic
+    /// 正方行列でない時
+    pub fn determinant(&self) -> ModInt {
+        assert!(self.is_square(), "determinant of non-square matrix");
+        let n = self.rows;
+        let mut a = self.data.clone();
+        let mut det = ModInt::new(1, self.modulus);
+        for col in 0..n {
+            let pivot = match (col..n).find(|&r| a[r][col].get() != 0) {
+                Some(r) => r,
+                None => return ModInt::new(0, self.modulus),
+            };
+            if pivot != col {
+                a.swap(pivot, col);
+                det = ModInt::new(0, self.modulus) - det;
+            }
+            det *= a[col][col];
+            let inv = a[col][col].checked_inv().expect("modulus must be prime");
+            for r in col + 1..n {
+                let factor = a[r][col] * inv;
+                if factor.get() == 0 {
+                    continue;
+                }
+                for c in col..n {
+                    let sub = factor * a[col][c];
+                    a[r][c] -= sub;
+                }
+            }
+        }
+        det
+    }
+
+    /// 逆行列
+    ///
+    /// 掃き出し法で`O(n^3)`、modは素数であること
+    ///
+    /// 正則でなければ`None`
+    ///
+    /// # Panic
+    /// 正方行列でない時
+    pub fn inverse(&self) -> Option<Matrix> {
+        assert!(self.is_square(), "inverse of non-square matrix");
+        let n = self.rows;
+        let mut a = self.data.clone();
+        let mut res = Self::identity(n, self.modulus);
+        for col in 0..n {
+            let pivot = (col..n).find(|&r| a[r][col].get() != 0)?;
+            a.swap(pivot, col);
+            res.data.swap(pivot, col);
+            let inv = a[col][col].checked_inv().expect("modulus must be prime");
+            for c in 0..n {
+                a[col][c] *= inv;
+                res.data[col][c] *= inv;
+            }
+            for r in 0..n {
+                if r == col || a[r][col].get() == 0 {
+                    continue;
+                }
+                let factor = a[r][col];
+                for c in 0..n {
+                    let sub = factor * a[col][c];
+                    a[r][c] -= sub;
+                    let sub = factor * res.data[col][c];
+                    res.data[r][c] -= sub;
+                }
+            }
+        }
+        Some(res)
+    }
+
+    /// 部分ピボット選択つきのLU分解 `PA = LU`
+    ///
+    /// `(L, U, perm)`を返す、`L`は対角成分が1の下三角、`U`は上三角、
+    /// `PA`の`i`行目は`A`の`perm[i]`行目
+    ///
+    /// 同じ係数行列で何度も解くときに一度だけ分解しておく
+    ///
+    /// `O(n^3)`、modは素数であること、正則でなければ`None`
+    ///
+    /// # Panic
+    /// 正方行列でない時
+    pub fn lu_decompose(&self) -> Option<(Matrix, Matrix, Vec<usize>)> {
+        assert!(self.is_square(), "LU decomposition of non-square matrix");
+        let n = self.rows;
+        // 対角より下に L の成分を詰めておき、行の入れ替えではまとめて動かす
+        let mut a = self.data.clone();
+        let mut perm = (0..n).collect::<Vec<_>>();
+        for col in 0..n {
+            let pivot = (col..n).find(|&r| a[r][col].get() != 0)?;
+            a.swap(pivot, col);
+            perm.swap(pivot, col);
+            let inv = a[col][col].checked_inv().expect("modulus must be prime");
+            let (top, bottom) = a.split_at_mut(col + 1);
+            let pivot_row = &top[col][col + 1..];
+            for row in bottom.iter_mut() {
+                let factor = row[col] * inv;
+                row[col] = factor;
+                axpy(&mut row[col + 1..], -factor, pivot_row);
+            }
+        }
+        let mut l = Self::identity(n, self.modulus);
+        let mut u = Self::new(n, n, self.modulus);
+        for (i, row) in a.into_iter().enumerate() {
+            for (j, x) in row.into_iter().enumerate() {
+                if j < i {
+                    l[(i, j)] = x;
+                } else {
+                    u[(i, j)] = x;
+                }
+            }
+        }
+        Some((l, u, perm))
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut res = Self::new(self.cols, self.rows, self.modulus);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                res[(j, i)] = self[(i, j)];
+            }
+        }
+        res
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = ModInt;
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        &self.data[i][j]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        &mut self.data[i][j]
+    }
+}
+
+/// `O(n^3)`
+///
+/// # Panic
+/// 左の列数と右の行数が異なる時
+impl<'a> Mul for &'a Matrix {
+    type Output = Matrix;
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert_eq!(self.cols, rhs.rows, "dimension mismatch");
+        let mut res = Matrix::new(self.rows, rhs.cols, self.modulus);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                for j in 0..rhs.cols {
+                    res.data[i][j] += a * rhs[(k, j)];
+                }
+            }
+        }
+        res
+    }
+}
+
+/// 行列木定理で全域木の個数を数える
+///
+/// `laplacian_minor`はグラフのラプラシアン行列(次数行列 - 隣接行列)から
+/// 任意の1つの行と同じ番号の列を取り除いたもの
+///
+/// modは素数であること、`O(n^3)`
+pub fn count_spanning_trees(laplacian_minor: &Matrix) -> ModInt {
+    laplacian_minor.determinant()
+}
+
+/// 階数、独立な行(制約)の数
+///
+/// ガウスの消去法で0でないピボットを数える、`O(rows * cols * min(rows, cols))`
+///
+/// modは素数であること
+pub fn matrix_rank(matrix: &Matrix) -> usize {
+    let mut a = matrix.data.clone();
+    let mut rank = 0;
+    for col in 0..matrix.cols {
+        let pivot = match (rank..matrix.rows).find(|&r| a[r][col].get() != 0) {
+            Some(r) => r,
+            None => continue,
+        };
+        a.swap(pivot, rank);
+        let inv = a[rank][col].checked_inv().expect("modulus must be prime");
+        let (top, bottom) = a.split_at_mut(rank + 1);
+        let pivot_row = &top[rank][col..];
+        for row in bottom.iter_mut() {
+            let factor = row[col] * inv;
+            axpy(&mut row[col..], -factor, pivot_row);
+        }
+        rank += 1;
+    }
+    rank
+}
+
+/// 下三角行列`l`について`l x = b`を前進代入で解く
+///
+/// 対角成分の逆元を掛けていくので`O(n^2)`、modは素数であること
+///
+/// # Panic
+/// `l`が正方でない時、`b`の長さが合わない時、対角成分に0がある時
+pub fn solve_lower_triangular(l: &Matrix, b: &[ModInt]) -> Vec<ModInt> {
+    assert!(l.is_square(), "matrix must be square");
+    assert_eq!(l.rows, b.len(), "dimension mismatch");
+    let mut x: Vec<ModInt> = Vec::with_capacity(b.len());
+    for i in 0..l.rows {
+        let s = (0..i).fold(ModInt::new(0, l.modulus) + b[i], |acc, j| {
+            acc - l[(i, j)] * x[j]
+        });
+        let inv = l[(i, i)]
+            .checked_inv()
+            .expect("diagonal entries must be invertible");
+        x.push(s * inv);
+    }
+    x
+}
+
+#[test]
+fn from_rows_test() {
+    let m = 1_000_000_007;
+    let rows = vec![
+        vec![ModInt::new(1, m), ModInt::new(2, m), ModInt::new(3, m)],
+        vec![ModInt::new(4, m), ModInt::new(5, m), ModInt::new(6, m)],
+    ];
+    let a = Matrix::from_rows(rows);
+    assert_eq!((a.rows(), a.cols(), a.modulus()), (2, 3, m));
+    assert_eq!(a[(1, 2)].get(), 6);
+    assert_eq!(a.row(0)[1].get(), 2);
+
+    let jagged = vec![vec![ModInt::new(1, m)], vec![]];
+    assert!(std::panic::catch_unwind(move || Matrix::from_rows(jagged)).is_err());
+    let mixed = vec![vec![ModInt::new(1, 7), ModInt::new(1, 11)]];
+    let err = std::panic::catch_unwind(move || Matrix::from_rows(mixed)).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<String>().unwrap(),
+        "modulus mismatch: lhs mod 7 vs rhs mod 11"
+    );
+    assert!(std::panic::catch_unwind(|| Matrix::from_rows(vec![])).is_err());
+}
+
+#[test]
+fn solve_lower_triangular_test() {
+    let m = 1_000_000_007;
+    let mint = |v: &[i64]| v.iter().map(|&x| ModInt::new(x, m)).collect::<Vec<_>>();
+    // [[2, 0, 0], [3, 1, 0], [-1, 4, 5]] x = [4, 7, 13]  =>  x = [2, 1, 11/5]
+    let l = Matrix::from_rows(vec![mint(&[2, 0, 0]), mint(&[3, 1, 0]), mint(&[-1, 4, 5])]);
+    let x = solve_lower_triangular(&l, &mint(&[4, 7, 13]));
+    assert_eq!(x[0].get(), 2);
+    assert_eq!(x[1].get(), 1);
+    assert_eq!(x[2], ModInt::from_fraction(11, 5, m));
+    for i in 0..3 {
+        let row = (0..3).fold(ModInt::new(0, m), |acc, j| acc + l[(i, j)] * x[j]);
+        assert_eq!(row, mint(&[4, 7, 13])[i]);
+    }
+    assert_eq!(
+        solve_lower_triangular(&Matrix::identity(2, m), &mint(&[5, -1])),
+        mint(&[5, -1])
+    );
+
+    let singular = Matrix::new(2, 2, m);
+    assert!(
+        std::panic::catch_unwind(|| solve_lower_triangular(&singular, &mint(&[1, 1]))).is_err()
+    );
+}
+
+#[test]
+fn lu_decompose_test() {
+    let m = 1_000_000_007;
+    let mint = |v: &[i64]| v.iter().map(|&x| ModInt::new(x, m)).collect::<Vec<_>>();
+    // 先頭で行の入れ替えが必要なもの
+    let a = Matrix::from_rows(vec![
+        mint(&[0, 2, 1, 4]),
+        mint(&[1, -1, 3, 0]),
+        mint(&[5, 4, -2, 1]),
+        mint(&[2, 0, 7, 3]),
+    ]);
+    let (l, u, perm) = a.lu_decompose().unwrap();
+    let mut pa = Matrix::new(4, 4, m);
+    for i in 0..4 {
+        for j in 0..4 {
+            pa[(i, j)] = a[(perm[i], j)];
+            if j > i {
+                assert_eq!(l[(i, j)].get(), 0);
+            }
+            if j < i {
+                assert_eq!(u[(i, j)].get(), 0);
+            }
+        }
+        assert_eq!(l[(i, i)].get(), 1);
+    }
+    assert_eq!(&l * &u, pa);
+
+    // 行列式 = 置換の符号 * Uの対角成分の積
+    let mut sign = ModInt::new(1, m);
+    let mut p = perm;
+    for i in 0..p.len() {
+        while p[i] != i {
+            let j = p[i];
+            p.swap(i, j);
+            sign = -sign;
+        }
+    }
+    let diag = (0..4).fold(sign, |acc, i| acc * u[(i, i)]);
+    assert_eq!(diag, a.determinant());
+
+    let singular = Matrix::from_rows(vec![mint(&[1, 2]), mint(&[2, 4])]);
+    assert!(singular.lu_decompose().is_none());
+}
+
+#[test]
+fn matrix_rank_test() {
+    let m = 1_000_000_007;
+    let mint = |v: &[i64]| v.iter().map(|&x| ModInt::new(x, m)).collect::<Vec<_>>();
+    assert_eq!(matrix_rank(&Matrix::new(3, 4, m)), 0);
+    assert_eq!(matrix_rank(&Matrix::identity(5, m)), 5);
+
+    // 3行目 = 1行目 + 2行目
+    let a = Matrix::from_rows(vec![
+        mint(&[1, 2, 3, 4]),
+        mint(&[0, 1, -1, 2]),
+        mint(&[1, 3, 2, 6]),
+    ]);
+    assert_eq!(matrix_rank(&a), 2);
+    assert_eq!(matrix_rank(&a.transpose()), 2);
+
+    // 先頭の列が0
+    let b = Matrix::from_rows(vec![mint(&[0, 0, 5]), mint(&[0, 2, 1])]);
+    assert_eq!(matrix_rank(&b), 2);
+    // mod 7 では 7 = 0
+    let c = Matrix::from_rows(vec![
+        vec![ModInt::new(1, 7), ModInt::new(2, 7)],
+        vec![ModInt::new(3, 7), ModInt::new(13, 7)],
+    ]);
+    assert_eq!(matrix_rank(&c), 1);
+}
+
+#[test]
+fn mul_vec_test() {
+    let m = 1_000_000_007;
+    let mint = |v: &[i64]| v.iter().map(|&x| ModInt::new(x, m)).collect::<Vec<_>>();
+    // [[1, 2, 3], [4, 5, 6]] * [1, 0, -1] = [-2, -2]
+    let a = Matrix::from_rows(vec![mint(&[1, 2, 3]), mint(&[4, 5, 6])]);
+    assert_eq!(a.mul_vec(&mint(&[1, 0, -1])), mint(&[-2, -2]));
+    assert_eq!(a.mul_vec(&mint(&[2, 1, 1])), mint(&[7, 19]));
+
+    // フィボナッチの遷移行列
+    let t = Matrix::from_rows(vec![mint(&[1, 1]), mint(&[1, 0])]);
+    let mut v = mint(&[1, 0]);
+    for _ in 0..10 {
+        v = t.mul_vec(&v);
+    }
+    assert_eq!(v, mint(&[89, 55]));
+    // 列数0の行列
+    assert_eq!(Matrix::new(2, 0, m).mul_vec(&[]), mint(&[0, 0]));
+
+    assert!(std::panic::catch_unwind(|| a.mul_vec(&mint(&[1, 2]))).is_err());
+    let other = vec![ModInt::new(1, 7); 3];
+    assert!(std::panic::catch_unwind(|| a.mul_vec(&other)).is_err());
+}
+
+#[test]
+fn matrix_mul_test() {
+    let m = 1_000_000_007;
+    let mut a = Matrix::new(2, 3, m);
+    for i in 0..2 {
+        for j in 0..3 {
+            a[(i, j)] = ModInt::new(i * 3 + j + 1, m);
+        }
+    }
+    // [[1, 2, 3], [4, 5, 6]] * [[1, 4], [2, 5], [3, 6]]
+    let p = &a * &a.transpose();
+    assert_eq!((p.rows(), p.cols()), (2, 2));
+    assert_eq!(p[(0, 0)].get(), 14);
+    assert_eq!(p[(0, 1)].get(), 32);
+    assert_eq!(p[(1, 0)].get(), 32);
+    assert_eq!(p[(1, 1)].get(), 77);
+    assert_eq!(&Matrix::identity(2, m) * &a, a);
+}
+
+#[test]
+fn determinant_test() {
+    let m = 1_000_000_007;
+    let mut a = Matrix::new(3, 3, m);
+    let v = [[2, -1, 0], [1, 3, 4], [0, 5, -2]];
+    for i in 0..3 {
+        for j in 0..3 {
+            a[(i, j)] = ModInt::new(v[i][j], m);
+        }
+    }
+    // 2(-6 - 20) + 1(-2 - 0) = -54
+    assert_eq!(a.determinant(), ModInt::new(-54, m));
+    assert_eq!(Matrix::identity(4, m).determinant().get(), 1);
+    assert_eq!(Matrix::new(2, 2, m).determinant().get(), 0);
+
+    // 行の入れ替えが必要なもの
+    let mut b = Matrix::new(2, 2, m);
+    b[(0, 1)] = ModInt::new(1, m);
+    b[(1, 0)] = ModInt::new(1, m);
+    assert_eq!(b.determinant(), ModInt::new(-1, m));
+}
+
+#[test]
+fn spanning_tree_test() {
+    let m = 1_000_000_007;
+    // K4 のラプラシアンから最後の行と列を除いたもの
+    let mut k4 = Matrix::new(3, 3, m);
+    for i in 0..3 {
+        for j in 0..3 {
+            k4[(i, j)] = ModInt::new(if i == j { 3 } else { -1 }, m);
+        }
+    }
+    assert_eq!(count_spanning_trees(&k4).get(), 16);
+
+    // 4頂点のサイクルは4個
+    let edges = [(0, 1), (1, 2), (2, 3), (3, 0)];
+    let mut lap = Matrix::new(4, 4, m);
+    for &(u, v) in &edges {
+        lap[(u, u)] += 1;
+        lap[(v, v)] += 1;
+        lap[(u, v)] -= 1;
+        lap[(v, u)] -= 1;
+    }
+    let mut minor = Matrix::new(3, 3, m);
+    for i in 0..3 {
+        for j in 0..3 {
+            minor[(i, j)] = lap[(i + 1, j + 1)];
+        }
+    }
+    assert_eq!(count_spanning_trees(&minor).get(), 4);
+}
+
+#[test]
+fn inverse_test() {
+    let m = 1_000_000_007;
+    let mut a = Matrix::new(2, 2, m);
+    a[(0, 0)] = ModInt::new(2, m);
+    a[(0, 1)] = ModInt::new(1, m);
+    a[(1, 0)] = ModInt::new(5, m);
+    a[(1, 1)] = ModInt::new(3, m);
+    // det = 1 なので [[3, -1], [-5, 2]]
+    let inv = a.inverse().unwrap();
+    assert_eq!(inv[(0, 0)], ModInt::new(3, m));
+    assert_eq!(inv[(0, 1)], ModInt::new(-1, m));
+    assert_eq!(inv[(1, 0)], ModInt::new(-5, m));
+    assert_eq!(inv[(1, 1)], ModInt::new(2, m));
+    assert_eq!(&a * &inv, Matrix::identity(2, m));
+
+    // 行の入れ替えが必要なもの
+    let mut b = Matrix::new(3, 3, m);
+    let v = [[0, 2, 1], [1, 0, 0], [3, 4, 5]];
+    for i in 0..3 {
+        for j in 0..3 {
+            b[(i, j)] = ModInt::new(v[i][j], m);
+        }
+    }
+    let inv = b.inverse().unwrap();
+    assert_eq!(&b * &inv, Matrix::identity(3, m));
+    assert_eq!(&inv * &b, Matrix::identity(3, m));
+
+    // 正則でない
+    let mut c = Matrix::new(2, 2, m);
+    c[(0, 0)] = ModInt::new(1, m);
+    c[(0, 1)] = ModInt::new(2, m);
+    c[(1, 0)] = ModInt::new(2, m);
+    c[(1, 1)] = ModInt::new(4, m);
+    assert!(c.inverse().is_none());
+}
@@ -0,0 +1,9 @@
+/// `mint!(x)` = `ModInt::new(x, default_modulus())`
+///
+/// modが1つだけのプログラムで毎回modを書かずに済ませる、modは`set_default_modulus`で設定する
+#[macro_export]
+macro_rules! mint {
+    ($x:expr) => {
+        $crate::modint::ModInt::new($x, $crate::modint::default_modulus())
+    };
+}
@@ -5,14 +5,19 @@ pub mod modint {
     use num_traits::identities::{One, Zero};
     use num_traits::{Num, Pow};
     use std::cmp::Ordering;
-    use std::convert::TryInto;
+    use std::collections::HashMap;
+    use std::convert::{TryFrom, TryInto};
+    use std::fmt;
+    use std::iter::{Product, Sum};
     use std::num::NonZeroU32;
     use std::num::ParseIntError;
+    use std::num::TryFromIntError;
     use std::ops::{
-        Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign,
+        Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
     };
+    use std::str::FromStr;
 
-    use crate::combinatorics::factorial::Factoriable;
+    use crate::combinatorics::Factoriable;
 
     /// n % m
     /// ただし答えが負になる場合は余分にmを足すことで一意な値を保証
@@ -28,19 +33,201 @@ pub mod modint {
         }
     }
 
+    /// 拡張ユークリッドの互除法 `(g, x, y)`、`a x + b y = g = gcd(a, b)`
+    ///
+    /// `g`は常に非負、中国剰余定理や一次合同式を解くときに
+    ///
+    /// `O(log min(|a|, |b|))`
+    ///
+    /// # Panic
+    /// `a`か`b`が`i64::MIN`の時(`|a|, |b| <= i64::MAX`であること)
+    pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+        // i64::MIN を含むと gcd が i64 に収まらないことがある
+        assert!(
+            a != std::i64::MIN && b != std::i64::MIN,
+            "ext_gcd argument out of range"
+        );
+        // (r, x, y) で a x + b y = r を保つ
+        let (mut r0, mut x0, mut y0) = (a, 1, 0);
+        let (mut r1, mut x1, mut y1) = (b, 0, 1);
+        while r1 != 0 {
+            let q = r0 / r1;
+            let next = (r0 - q * r1, x0 - q * x1, y0 - q * y1);
+            r0 = r1;
+            x0 = x1;
+            y0 = y1;
+            r1 = next.0;
+            x1 = next.1;
+            y1 = next.2;
+        }
+        if r0 < 0 {
+            (-r0, -x0, -y0)
+        } else {
+            (r0, x0, y0)
+        }
+    }
+
+    #[test]
+    fn ext_gcd_test() {
+        let pairs = [
+            (240, 46),
+            (46, 240),
+            (-240, 46),
+            (240, -46),
+            (-240, -46),
+            (17, 5),
+            (0, 7),
+            (7, 0),
+            (0, 0),
+            (1, 1),
+            (1_000_000_007, 998_244_353),
+            (-6, 9),
+            (std::i64::MAX, std::i64::MAX - 1),
+            (-std::i64::MAX, std::i64::MAX),
+            (std::i64::MAX, 0),
+            (-std::i64::MAX, -1),
+        ];
+        for &(a, b) in &pairs {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(g, a.gcd(&b), "{} {}", a, b);
+            assert!(g >= 0);
+            assert_eq!(
+                a as i128 * x as i128 + b as i128 * y as i128,
+                g as i128,
+                "{} {}",
+                a,
+                b
+            );
+        }
+        assert_eq!(ext_gcd(240, 46).0, 2);
+        assert_eq!(ext_gcd(-7, 0), (7, -1, 0));
+
+        // 定義域外
+        assert!(std::panic::catch_unwind(|| ext_gcd(std::i64::MIN, -1)).is_err());
+        assert!(std::panic::catch_unwind(|| ext_gcd(std::i64::MIN, 0)).is_err());
+        assert!(std::panic::catch_unwind(|| ext_gcd(0, std::i64::MIN)).is_err());
+    }
+
+    /// 剰余をとった後の値を`i64`に戻す
+    ///
+    /// `overflow_checks` featureが有効なら範囲外でpanicする、無効なら単なる`as`
+    #[cfg(feature = "overflow_checks")]
+    fn narrow(x: u128) -> i64 {
+        i64::try_from(x).expect("reduced value out of i64 range")
+    }
+
+    #[cfg(not(feature = "overflow_checks"))]
+    #[inline]
+    fn narrow(x: u128) -> i64 {
+        x as i64
+    }
+
+    /// `a * b % m`
+    ///
+    /// `m`は`u32`に収まるが積は`i64`を溢れうるので`u128`で計算する
+    fn mul_mod(a: i64, b: i64, m: usize) -> i64 {
+        debug_assert!(a >= 0 && b >= 0);
+        narrow(a as u128 * b as u128 % m as u128)
+    }
+
+    #[test]
+    fn mul_mod_wide_test() {
+        // u32に収まる最大の素数
+        let m = 4_294_967_291u32;
+        let a = ModInt::new(4_294_967_290u32, m); // -1
+        assert_eq!((a * a).get(), 1);
+        assert_eq!(a / (a * a), a);
+        assert_eq!(
+            mul_mod(3_000_000_000, 3_000_000_000, 4_294_967_291),
+            1_392_778_655
+        );
+    }
+
+    #[cfg(feature = "overflow_checks")]
+    #[test]
+    fn overflow_checks_test() {
+        let m = 1_000_000_007;
+        let a = ModInt::new(123_456_789, m);
+        assert_eq!((a * a).get(), 123_456_789i64 * 123_456_789 % m as i64);
+        assert_eq!(narrow(std::i64::MAX as u128), std::i64::MAX);
+
+        let res = std::panic::catch_unwind(|| narrow(std::i64::MAX as u128 + 1));
+        assert!(res.is_err());
+    }
+
+    /// よく使うmodとその原始根
+    pub mod moduli {
+        pub const MOD_998244353: u32 = 998_244_353;
+        pub const MOD_1E9_7: u32 = 1_000_000_007;
+        pub const MOD_1E9_9: u32 = 1_000_000_009;
+
+        pub const PRIMITIVE_ROOT_998244353: u32 = 3;
+        pub const PRIMITIVE_ROOT_1E9_7: u32 = 5;
+        pub const PRIMITIVE_ROOT_1E9_9: u32 = 13;
+
+        /// 上のmodであればその原始根
+        pub fn known_primitive_root(modulus: u32) -> Option<u32> {
+            match modulus {
+                MOD_998244353 => Some(PRIMITIVE_ROOT_998244353),
+                MOD_1E9_7 => Some(PRIMITIVE_ROOT_1E9_7),
+                MOD_1E9_9 => Some(PRIMITIVE_ROOT_1E9_9),
+                _ => None,
+            }
+        }
+
+        #[test]
+        fn moduli_test() {
+            use crate::prime::trial_division::is_prime;
+
+            assert_eq!(MOD_998244353, 998_244_353);
+            assert_eq!(MOD_1E9_7, 1_000_000_007);
+            assert_eq!(MOD_1E9_9, 1_000_000_009);
+            for &m in &[MOD_998244353, MOD_1E9_7, MOD_1E9_9] {
+                assert!(is_prime(m as usize));
+                assert!(known_primitive_root(m).is_some());
+            }
+            assert_eq!(known_primitive_root(998_244_353), Some(3));
+            assert_eq!(known_primitive_root(13), None);
+        }
+    }
+
     #[derive(Debug, Copy, Clone, Eq, PartialEq)]
     pub enum Modulo {
         Static(NonZeroU32),
+        /// 素数であることが分かっている`Static`
+        StaticPrime(NonZeroU32),
         Dynamic,
     }
 
     impl Modulo {
+        // `Modulo::get(&m)`のような呼び出しがあるので参照のまま
+        #[allow(clippy::trivially_copy_pass_by_ref)]
         pub fn get(&self) -> Option<u32> {
             match self {
-                Modulo::Static(nz) => Some(nz.get()),
+                Modulo::Static(nz) | Modulo::StaticPrime(nz) => Some(nz.get()),
                 Modulo::Dynamic => None,
             }
         }
+
+        pub fn is_known_prime(self) -> bool {
+            matches!(self, Modulo::StaticPrime(_))
+        }
+    }
+
+    impl fmt::Display for Modulo {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                Modulo::Static(nz) | Modulo::StaticPrime(nz) => write!(f, "{}", nz),
+                Modulo::Dynamic => write!(f, "dynamic"),
+            }
+        }
+    }
+
+    #[test]
+    fn modulo_get_test() {
+        let m = ModInt::new(3, 7)._modulo;
+        assert_eq!(Modulo::get(&m), Some(7));
+        assert_eq!(Modulo::get(&Modulo::Dynamic), None);
     }
 
     /// `ModInt -> PrimiteveInt` への暗黙のキャストは行わない!
@@ -53,12 +240,90 @@ pub mod modint {
         _modulo: Modulo,
     }
 
+    /// 剰余を10進で
+    impl fmt::Display for ModInt {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{}", self.get())
+        }
+    }
+
+    impl From<ModInt> for String {
+        fn from(x: ModInt) -> String {
+            x.to_string()
+        }
+    }
+
+    #[test]
+    fn to_string_test() {
+        let m = 1_000_000_007;
+        assert_eq!(String::from(ModInt::new(0, m)), "0");
+        assert_eq!(String::from(ModInt::new(-1, m)), "1000000006");
+        let s: String = ModInt::new(123, 100).into();
+        assert_eq!(s, "23");
+        assert_eq!(
+            format!("{} {}", ModInt::new(3, 7), ModInt::new(10, 7)),
+            "3 3"
+        );
+
+        fn takes_into_string<S: Into<String>>(s: S) -> String {
+            s.into()
+        }
+        assert_eq!(takes_into_string(ModInt::new(42, m)), "42");
+    }
+
+    /// 剰余を`BigInt`に
+    #[cfg(feature = "bigint")]
+    impl From<ModInt> for num_bigint::BigInt {
+        fn from(x: ModInt) -> num_bigint::BigInt {
+            num_bigint::BigInt::from(x.get())
+        }
+    }
+
+    #[cfg(feature = "bigint")]
+    impl ModInt {
+        /// `(剰余, mod)`を`BigInt`で
+        ///
+        /// # Panic
+        /// if variant is Modulo::Dynamic
+        pub fn to_bigint_with_modulus(&self) -> (num_bigint::BigInt, num_bigint::BigInt) {
+            (
+                num_bigint::BigInt::from(self.get()),
+                num_bigint::BigInt::from(self.get_mod()),
+            )
+        }
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn bigint_test() {
+        use num_bigint::BigInt;
+
+        let x = ModInt::new(-1, 1_000_000_007);
+        assert_eq!(BigInt::from(x), BigInt::from(1_000_000_006));
+        let (r, m) = x.to_bigint_with_modulus();
+        assert_eq!(r, BigInt::from(1_000_000_006));
+        assert_eq!(m, BigInt::from(1_000_000_007));
+    }
+
     impl Into<usize> for ModInt {
         fn into(self) -> usize {
+            debug_assert!(self.get() >= 0, "inner value is negative: {}", self.get());
             self.get() as usize
         }
     }
 
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn negative_into_usize() {
+        // 不変条件が壊れた状態をわざと作る
+        let m = ModInt {
+            num: -1,
+            _modulo: Modulo::Dynamic,
+        };
+        let _: usize = m.into();
+    }
+
     pub trait IntoModInt: Copy {
         fn to_mint<M: TryInto<u32> + Copy>(self, modulo: M) -> ModInt;
     }
@@ -77,12 +342,11 @@ pub mod modint {
 
     impl_into_mint!(usize, u8, u16, u32, u64, isize, i8, i16, i32, i64);
 
+    /// 片方が`Dynamic`なら相手のmodで割った余りどうしで比較する
     impl PartialEq for ModInt {
         fn eq(&self, other: &Self) -> bool {
-            if !check_mod_eq(self, other).1 {
-                panic!("cannot compare these values because they have different modulo number",)
-            }
-            self.get() == other.num
+            let (lhs, rhs) = unify(*self, *other);
+            lhs.get() == rhs.get()
         }
     }
 
@@ -94,7 +358,8 @@ pub mod modint {
             if !check_mod_eq(self, other).1 {
                 None
             } else {
-                Some(self.get().cmp(&other.num))
+                let (lhs, rhs) = unify(*self, *other);
+                Some(lhs.get().cmp(&rhs.get()))
             }
         }
     }
@@ -106,21 +371,77 @@ pub mod modint {
     //     }
     // }
 
+    /// 異なるmod間での演算をattemptした時に、両方のmodを添えてpanicする
+    pub(crate) fn modulo_mismatch(lhs: &ModInt, rhs: &ModInt) -> ! {
+        panic!(
+            "modulus mismatch: lhs mod {} vs rhs mod {}",
+            lhs._modulo, rhs._modulo
+        )
+    }
+
+    #[test]
+    fn mismatch_message_test() {
+        let a = ModInt::new(3, 7);
+        let b = ModInt::new(3, 13);
+        let err = std::panic::catch_unwind(|| a + b).unwrap_err();
+        let msg = err.downcast_ref::<String>().unwrap();
+        assert!(msg.contains('7'));
+        assert!(msg.contains("13"));
+        assert_eq!(msg, "modulus mismatch: lhs mod 7 vs rhs mod 13");
+    }
+
+    /// `Static(p)`と`StaticPrime(p)`は同じmodとみなす
     fn check_mod_eq(a: &ModInt, b: &ModInt) -> (NonZeroU32, bool) {
-        match (a._modulo, b._modulo) {
-            (Modulo::Static(a), Modulo::Static(b)) => {
+        // safe becase 1 != 0, yeah
+        let dummy = unsafe { NonZeroU32::new_unchecked(1) };
+        match (a._modulo.get(), b._modulo.get()) {
+            (Some(a), Some(b)) => {
                 if a == b {
-                    (a, true)
+                    (NonZeroU32::new(a).unwrap(), true)
                 } else {
-                    // safe becase 1 != 0, yeah
-                    (unsafe { NonZeroU32::new_unchecked(1) }, false)
+                    (dummy, false)
                 }
             }
-            (Modulo::Static(m), Modulo::Dynamic) | (Modulo::Dynamic, Modulo::Static(m)) => {
-                (m, true)
-            }
-            (Modulo::Dynamic, Modulo::Dynamic) => (unsafe { NonZeroU32::new_unchecked(1) }, false),
+            (Some(m), None) | (None, Some(m)) => (NonZeroU32::new(m).unwrap(), true),
+            (None, None) => (dummy, false),
+        }
+    }
+
+    /// 二項演算の前に両辺のmodを揃える
+    ///
+    /// `Dynamic`な側は相手のmodで割った余りにして`Static`として扱う
+    ///
+    /// どちらかが`StaticPrime`なら両辺とも`StaticPrime`にする
+    ///
+    /// # Panic
+    /// 異なるmod間での演算をattemptした時
+    fn unify(lhs: ModInt, rhs: ModInt) -> (ModInt, ModInt) {
+        let (m, ok) = check_mod_eq(&lhs, &rhs);
+        if !ok {
+            modulo_mismatch(&lhs, &rhs)
         }
+        let modulo = if lhs._modulo.is_known_prime() || rhs._modulo.is_known_prime() {
+            Modulo::StaticPrime(m)
+        } else {
+            Modulo::Static(m)
+        };
+        let adopt = |x: ModInt| ModInt {
+            num: match x._modulo {
+                Modulo::Dynamic => compensated_rem(x.num, m.get() as usize),
+                _ => x.num,
+            },
+            _modulo: modulo,
+        };
+        (adopt(lhs), adopt(rhs))
+    }
+
+    /// 剰余をどの完全代表系で表すか
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Representative {
+        /// `[0, m)`、`get`と同じ
+        LeastNonNegative,
+        /// `(-m/2, m/2]`、絶対値が最小のもの
+        LeastAbsolute,
     }
 
     impl ModInt {
@@ -139,11 +460,117 @@ pub mod modint {
             }
         }
 
+        /// modが素数であることを記録した`ModInt`を作る
+        ///
+        /// 逆元などで素数専用の方法を使い、素数判定を省略する
+        ///
+        /// `p`が素数であるかは検査しないので、呼び出し側が保証すること
+        pub fn new_prime<N: TryInto<i64>>(n: N, p: u32) -> Self {
+            Self {
+                _modulo: Modulo::StaticPrime(
+                    NonZeroU32::new(p).expect("modulo number may be wrong"),
+                ),
+                ..Self::new(n, p)
+            }
+        }
+
+        /// `new_prime`で作られた(modが素数と分かっている)か
+        pub fn is_prime_modulus_known(&self) -> bool {
+            self._modulo.is_known_prime()
+        }
+
+        /// `b`が`true`なら1、`false`なら0
+        ///
+        /// 数え上げDPで指示関数を足し込むときに
+        pub fn from_bool(b: bool, modulus: u32) -> Self {
+            Self::new(b as i64, modulus)
+        }
+
+        /// 10進の数字`b'0'..=b'9'`の値、数字でなければ`None`
+        ///
+        /// 桁DPなどでバイト列を走査するときに
+        pub fn from_ascii_digit(c: u8, modulus: u32) -> Option<Self> {
+            if c.is_ascii_digit() {
+                Some(Self::new(c - b'0', modulus))
+            } else {
+                None
+            }
+        }
+
+        /// 分数`num / den`の剰余
+        ///
+        /// 答えを`p/q mod M`で出力させる問題に
+        ///
+        /// # Panic
+        /// `den`がmodと互いに素でない時
+        pub fn from_fraction(num: i64, den: i64, modulus: u32) -> Self {
+            let den = Self::new(den, modulus)
+                .checked_inv()
+                .expect("denominator is not invertible");
+            Self::new(num, modulus) * den
+        }
+
+        /// 整数値の浮動小数点数から作る
+        ///
+        /// 有限でない時、最も近い整数から`1e-9`より離れている時、`i64`に収まらない時は`None`
+        ///
+        /// `f64`の仮数部は53bitなので、`2^53`を超える値は元の整数を復元できるとは限らない
+        pub fn from_f64(x: f64, modulus: u32) -> Option<Self> {
+            const EPS: f64 = 1e-9;
+            if !x.is_finite() {
+                return None;
+            }
+            let r = x.round();
+            if (x - r).abs() > EPS || r.abs() >= std::i64::MAX as f64 {
+                return None;
+            }
+            Some(Self::new(r as i64, modulus))
+        }
+
         /// get inner value
         pub fn get(&self) -> i64 {
             self.num
         }
 
+        /// `self`と`value`をmodで割った余りが等しいか
+        ///
+        /// `ModInt`を作らずに比べる、負の`value`も正しく扱う
+        pub fn equals_int(&self, value: i64) -> bool {
+            self.num == compensated_rem(value, self.get_mod())
+        }
+
+        /// `system`で選んだ代表系での値
+        ///
+        /// `LeastAbsolute`でmodが偶数のとき、`m/2`は正の方をとる
+        pub fn representative(&self, system: Representative) -> i64 {
+            match system {
+                Representative::LeastNonNegative => self.get(),
+                Representative::LeastAbsolute => {
+                    let m = self.get_mod() as i64;
+                    if self.get() > m / 2 {
+                        self.get() - m
+                    } else {
+                        self.get()
+                    }
+                }
+            }
+        }
+
+        /// 0に最も近い代表 (`representative(LeastAbsolute)`)
+        ///
+        /// 中国剰余定理で復元した値が負かもしれないときのデバッグ用、
+        /// `|x| < m/2`の整数`x`から作った値なら`x`そのものに戻る
+        pub fn signed_residue(&self) -> i64 {
+            self.representative(Representative::LeastAbsolute)
+        }
+
+        /// `usize`への変換
+        ///
+        /// 内部の値が負になっているとき(不変条件が壊れているとき)は`Err`
+        pub fn try_into_usize(&self) -> Result<usize, TryFromIntError> {
+            usize::try_from(self.get())
+        }
+
         /// mod of modint
         ///
         /// # Pani,c
@@ -152,6 +579,11 @@ pub mod modint {
             self._modulo.get().unwrap() as usize
         }
 
+        /// mod of modint, `None` if variant is Modulo::Dynamic
+        pub fn try_get_mod(&self) -> Option<usize> {
+            self._modulo.get().map(|m| m as usize)
+        }
+
         /// return the power of self with mod, using binary powering method
         /// cannot use of Dynamic type mod Self
         fn pow_mod(&self, mut exp: usize) -> Self {
@@ -168,7 +600,7 @@ pub mod modint {
                 exp >>= 1;
             }
 
-            Self::new(res, self.get_mod())
+            self.lift(res as i64)
         }
 
         /// `a / b == a * b^(-1)` となる `b^(-1)` を求める
@@ -191,137 +623,834 @@ pub mod modint {
             // if u < 0 { u += m; }
             // u
 
-            // impl with num_integar::Integar::extended_gcd ...
-            let x = self.get().extended_gcd(&(self.get_mod() as i64)).x;
+            // modが素数ならフェルマーの小定理
+            if self.is_prime_modulus_known() {
+                return self.pow_mod(self.get_mod() - 2).get();
+            }
+
+            let (_, x, _) = ext_gcd(self.get(), self.get_mod() as i64);
             compensated_rem(x, self.get_mod())
         }
-    }
-
-    #[test]
-    fn mint_new() {
-        let m = ModInt::new(10, 3);
-        assert_eq!(m.get(), 1);
-
-        let m = ModInt::new(-10, 3);
-        assert_eq!(m.get(), 2);
-
-        let x = 4.to_mint(10); // this is also valid
-        let y = ModInt::new(4, 10);
-        assert_eq!(x, y);
-    }
 
-    #[test]
-    fn inv_test() {
-        let a = ModInt::new(6, 13);
-        assert_eq!(a.inv(), 11);
-    }
+        /// 逆元が存在すれば`Some`、`self`とmodが互いに素でなければ`None`
+        pub fn checked_inv(&self) -> Option<Self> {
+            let m = self.get_mod();
+            if self.is_prime_modulus_known() {
+                return if self.get() == 0 {
+                    None
+                } else {
+                    Some(self.pow_mod(m - 2))
+                };
+            }
+            let (g, x, _) = ext_gcd(self.get(), m as i64);
+            if g == 1 {
+                Some(self.lift(x))
+            } else {
+                None
+            }
+        }
 
-    impl Add<Self> for ModInt {
-        type Output = Self;
-        fn add(self, rhs: Self) -> Self::Output {
-            let c = check_mod_eq(&self, &rhs);
-            if !c.1 {
-                panic!("modulo between two instance is different!",)
+        /// 逆元、modが素数ならフェルマーの小定理 `self^(m-2)`、そうでなければ`checked_inv`
+        ///
+        /// 素数判定(試し割り)の結果は直前のmodについてだけスレッドごとに覚えておくので、
+        /// 同じmodで続けて呼ぶときは判定し直さない
+        ///
+        /// 逆元がなければ`None`
+        pub fn inv_checked_prime(&self) -> Option<Self> {
+            thread_local! {
+                static LAST: std::cell::Cell<Option<(usize, bool)>> = std::cell::Cell::new(None);
+            }
+            let m = self.get_mod();
+            let prime = self.is_prime_modulus_known()
+                || LAST.with(|last| match last.get() {
+                    Some((lm, p)) if lm == m => p,
+                    _ => {
+                        let p = crate::prime::trial_division::is_prime(m);
+                        last.set(Some((m, p)));
+                        p
+                    }
+                });
+            if !prime {
+                return self.checked_inv();
             }
+            if self.get() == 0 {
+                None
+            } else {
+                Some(self.pow_mod(m - 2))
+            }
+        }
 
-            let r = self.get() + rhs.num;
-            Self {
-                num: if r >= self.get_mod() as i64 {
-                    r - c.0.get() as i64
-                } else {
-                    r
-                },
-                _modulo: Modulo::Static(c.0),
+        /// modが`2^k`の時の逆元をニュートン法 `x <- x(2 - ax)` で求める
+        ///
+        /// 奇数`a`は`a * a = 1 (mod 8)`なので`x = a`から始めると、1回ごとに正しいビット数が倍になる
+        ///
+        /// modが2冪でない時、`self`が偶数の時は`None`
+        pub fn inv_pow2(&self) -> Option<Self> {
+            let m = self.get_mod() as u64;
+            if !m.is_power_of_two() {
+                return None;
             }
+            if m == 1 {
+                return Some(*self);
+            }
+            let a = self.get() as u64;
+            if a % 2 == 0 {
+                return None;
+            }
+            // 3 -> 6 -> 12 -> 24 -> 48 bit
+            let mut x = a;
+            for _ in 0..4 {
+                x = x.wrapping_mul(2u64.wrapping_sub(a.wrapping_mul(x)));
+            }
+            Some(self.lift((x & (m - 1)) as i64))
         }
-    }
 
-    impl AddAssign<Self> for ModInt {
-        fn add_assign(&mut self, rhs: Self) {
-            *self = *self + rhs;
+        /// 失敗しうる演算`f`をつなげる (`Some(self).and_then(f)`)
+        ///
+        /// `checked_inv`や`try_div`などを続けて書くときに
+        pub fn and_then_mod<F: FnOnce(ModInt) -> Option<ModInt>>(self, f: F) -> Option<ModInt> {
+            f(self)
         }
-    }
 
-    #[test]
-    fn mint_add() {
-        let a = ModInt::new(13, 8); // 5
-        let b = ModInt::new(10, 8); // 2
-        assert_eq!((a + b).get(), 7);
+        /// modが揃っていれば2つを組にする、異なれば`None`
+        ///
+        /// `Dynamic`な側は相手のmodで扱う、`Dynamic`同士も`None`
+        pub fn zip_mod(self, rhs: ModInt) -> Option<(ModInt, ModInt)> {
+            if check_mod_eq(&self, &rhs).1 {
+                Some(unify(self, rhs))
+            } else {
+                None
+            }
+        }
 
-        let c = ModInt::new(7, 8); //7
-        assert_eq!((a + c).get(), 4); // (5 + 7) % 8 == 4
-    }
+        /// `self / rhs`、`rhs`が逆元を持たなければ`None`
+        ///
+        /// # Panic
+        /// 異なるmod間での演算をattemptした時
+        pub fn try_div(self, rhs: ModInt) -> Option<ModInt> {
+            let (lhs, rhs) = unify(self, rhs);
+            rhs.checked_inv().map(|inv| lhs * inv)
+        }
 
-    impl Sub<Self> for ModInt {
-        type Output = Self;
-        fn sub(self, rhs: Self) -> Self::Output {
-            let c = check_mod_eq(&self, &rhs);
-            if !c.1 {
-                panic!("modulo between two instance is different!",)
-            }
-            let num = compensated_rem(self.get() - rhs.get(), c.0.get() as usize);
-            Self {
-                num,
-                _modulo: Modulo::Static(c.0),
+        /// `(self / rhs, 割れたか)`
+        ///
+        /// `rhs`が逆元を持たなければ`(0, false)`、ガウスの消去法などでpanicさせずにピボットの特異性を調べるときに
+        ///
+        /// # Panic
+        /// 異なるmod間での演算をattemptした時
+        pub fn div_reporting(self, rhs: ModInt) -> (ModInt, bool) {
+            let (lhs, rhs) = unify(self, rhs);
+            match lhs.try_div(rhs) {
+                Some(q) => (q, true),
+                None => (lhs.lift(0), false),
             }
         }
-    }
 
-    impl SubAssign<Self> for ModInt {
-        fn sub_assign(&mut self, rhs: Self) {
-            *self = *self - rhs;
+        /// `(剰余を整数として割った商, 剰余環での self / d)`
+        ///
+        /// 前者は`get() / d`(0方向への切り捨て)、後者は`d`の逆元を掛けたもの
+        ///
+        /// # Panic
+        /// `d == 0`の時、`d`が逆元を持たない時
+        pub fn div_rem_integer(&self, d: i64) -> (i64, ModInt) {
+            assert!(d != 0, "division by zero");
+            (self.get() / d, *self / d)
         }
-    }
-
-    #[test]
-    fn mint_sub() {
-        let a = ModInt::new(2, 10);
-        let b = ModInt::new(3, 10);
 
-        assert_eq!((b - a).get(), 1);
-        assert_eq!((a - b).get(), 9);
-    }
+        /// modが素数か、`new_prime`で作られていれば判定を省略する
+        fn is_prime_modulus(&self) -> bool {
+            self.is_prime_modulus_known() || crate::prime::trial_division::is_prime(self.get_mod())
+        }
 
-    impl Mul<Self> for ModInt {
-        type Output = Self;
-        fn mul(self, rhs: Self) -> Self::Output {
-            let c = check_mod_eq(&self, &rhs);
-            if !c.1 {
-                panic!("modulo between two instance is different!",)
-            }
-            let num = compensated_rem(self.get() * rhs.get(), c.0.get() as usize);
-            Self {
-                num,
-                _modulo: Modulo::Static(c.0),
+        /// 平方剰余かどうかをオイラーの規準 `self^((p-1)/2) == 1` で判定する
+        ///
+        /// 0は平方剰余とみなす
+        ///
+        /// modが奇素数でなければ`None`、素数判定に試し割りを使うので`O(sqrt m)`
+        pub fn is_quadratic_residue(&self) -> Option<bool> {
+            let m = self.get_mod();
+            if m % 2 == 0 || !self.is_prime_modulus() {
+                return None;
             }
+            Some(self.legendre_symbol() != -1)
         }
-    }
 
-    impl MulAssign<Self> for ModInt {
-        fn mul_assign(&mut self, rhs: Self) {
-            *self = *self * rhs
+        /// 乗法群の生成元(原始根)かどうか
+        ///
+        /// `p - 1`の各素因数`q`について`self^((p-1)/q) != 1`を確かめる
+        ///
+        /// modが素数でない時、`self`が0の時は`None`
+        pub fn is_primitive_root(&self) -> Option<bool> {
+            let p = self.get_mod();
+            if !self.is_prime_modulus() || self.get() == 0 {
+                return None;
+            }
+            let phi = p - 1;
+            Some(
+                crate::prime::trial_division::factorization(phi)
+                    .into_iter()
+                    .all(|(q, _)| self.pow_mod(phi / q).get() != 1),
+            )
         }
-    }
 
-    impl Div<Self> for ModInt {
-        type Output = Self;
-        fn div(self, rhs: Self) -> Self::Output {
-            let c = check_mod_eq(&self, &rhs);
-            if !c.1 {
-                panic!("modulo between two instance is different!",)
-            }
-            Self {
-                num: self.get() * rhs.inv() % c.0.get() as i64,
-                _modulo: Modulo::Static(c.0),
+        /// ルジャンドル記号 `(self / p)`
+        ///
+        /// オイラーの規準 `self^((p-1)/2)` で、0なら0、平方剰余なら1、平方非剰余なら-1
+        ///
+        /// modは奇素数であること (素数かどうかは検査しない)
+        ///
+        /// # Panic
+        /// modが偶数の時、`self^((p-1)/2)`が`0, 1, -1`のどれでもなく素数でないと分かった時
+        pub fn legendre_symbol(&self) -> i8 {
+            let p = self.get_mod();
+            assert!(p % 2 == 1, "modulus must be an odd prime");
+            match self.pow_mod((p - 1) / 2).get() {
+                0 => 0,
+                1 => 1,
+                r if r == p as i64 - 1 => -1,
+                _ => panic!("modulus must be an odd prime"),
             }
         }
-    }
 
-    impl DivAssign<Self> for ModInt {
-        fn div_assign(&mut self, rhs: Self) {
-            *self = *self / rhs;
+        /// `self^k = 1`となる最小の正の`k` (乗法的位数)
+        ///
+        /// 位数は`phi(m)`の約数なので、`phi(m)`から素因数を取り除けるだけ取り除く
+        ///
+        /// `self`とmodが互いに素でなければ`None`、試し割りを使うので`O(sqrt m)`
+        pub fn multiplicative_order(&self) -> Option<u64> {
+            let m = self.get_mod();
+            if self.get().gcd(&(m as i64)) != 1 {
+                return None;
+            }
+            let phi = crate::prime::trial_division::factorization(m)
+                .into_iter()
+                .fold(m, |acc, (p, _)| acc / p * (p - 1));
+            let mut order = phi;
+            for (q, _) in crate::prime::trial_division::factorization(phi) {
+                while order % q == 0 && self.pow_mod(order / q).get() == 1 % m as i64 {
+                    order /= q;
+                }
+            }
+            Some(order as u64)
         }
-    }
+
+        /// 10進の文字列で与えられた巨大な指数`exp_decimal`での冪
+        ///
+        /// 一般化されたオイラーの定理により、`e >= phi(m)`なら
+        /// `a^e = a^(e mod phi(m) + phi(m))` (`a`とmが互いに素でなくても成り立つ)
+        ///
+        /// `phi(m)`のために試し割りするので `O(sqrt m + len(exp_decimal))`
+        ///
+        /// # Panic
+        /// `exp_decimal`が空、または数字以外を含む時
+        pub fn pow_bigexp(&self, exp_decimal: &str) -> ModInt {
+            assert!(!exp_decimal.is_empty(), "exponent must not be empty");
+            let m = self.get_mod();
+            let phi = crate::prime::trial_division::factorization(m)
+                .into_iter()
+                .fold(m, |acc, (p, _)| acc / p * (p - 1));
+            let mut rem = 0usize;
+            let mut large = false;
+            for c in exp_decimal.chars() {
+                let d = c.to_digit(10).expect("exponent must be decimal digits") as usize;
+                let next = rem * 10 + d;
+                large |= next >= phi;
+                rem = next % phi;
+            }
+            if large {
+                self.pow_mod(rem + phi)
+            } else {
+                self.pow_mod(rem)
+            }
+        }
+
+        /// `1, self, self^2, ...` を遅延評価で列挙する無限Iterator
+        ///
+        /// 1項進めるごとに乗算1回
+        pub fn powers(self) -> impl Iterator<Item = ModInt> {
+            std::iter::successors(Some(self.lift(1)), move |&p| Some(p * self))
+        }
+
+        /// `self * w^k`、`roots = [1, w, w^2, ..., w^(n-1)]`は`w^n = 1`となる冪根の表
+        ///
+        /// NTTのバタフライの内側で使うので`k mod n`はとらない、必要なら呼び出し側で
+        ///
+        /// # Panic
+        /// `k >= n`の時
+        #[inline]
+        pub fn mul_root_power(&self, roots: &[ModInt], k: usize) -> ModInt {
+            *self * roots[k]
+        }
+
+        /// `self * self`
+        ///
+        /// modの一致を確かめず、直接`u128`で掛けて剰余をとる
+        pub fn square(self) -> Self {
+            Self {
+                num: mul_mod(self.num, self.num, self.get_mod()),
+                ..self
+            }
+        }
+    }
+
+    #[test]
+    fn is_quadratic_residue_test() {
+        // mod 11 の平方剰余は 1, 3, 4, 5, 9
+        let res = (0..11)
+            .filter(|&i| ModInt::new(i, 11).is_quadratic_residue().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(res, vec![0, 1, 3, 4, 5, 9]);
+
+        let p = 1_000_000_007;
+        assert_eq!(ModInt::new(4, p).is_quadratic_residue(), Some(true));
+        // -1 は p = 3 (mod 4) で非剰余
+        assert_eq!(ModInt::new(-1, p).is_quadratic_residue(), Some(false));
+        assert_eq!(ModInt::new(-1, 13).is_quadratic_residue(), Some(true));
+
+        assert_eq!(ModInt::new(3, 2).is_quadratic_residue(), None);
+        assert_eq!(ModInt::new(3, 15).is_quadratic_residue(), None);
+        assert_eq!(ModInt::new(3, 16).is_quadratic_residue(), None);
+    }
+
+    #[test]
+    fn multiplicative_order_test() {
+        assert_eq!(ModInt::new(3, 7).multiplicative_order(), Some(6));
+        assert_eq!(ModInt::new(2, 7).multiplicative_order(), Some(3));
+        assert_eq!(ModInt::new(6, 7).multiplicative_order(), Some(2));
+        assert_eq!(ModInt::new(1, 7).multiplicative_order(), Some(1));
+        assert_eq!(ModInt::new(0, 7).multiplicative_order(), None);
+        // 合成数のmod
+        assert_eq!(ModInt::new(5, 12).multiplicative_order(), Some(2));
+        assert_eq!(ModInt::new(4, 12).multiplicative_order(), None);
+        assert_eq!(ModInt::new(0, 1).multiplicative_order(), Some(1));
+        for a in 1..31 {
+            let a = ModInt::new(a, 31);
+            let k = a.multiplicative_order().unwrap() as usize;
+            assert!(a.pow(k).is_one());
+            assert!((1..k).all(|j| !a.pow(j).is_one()));
+        }
+    }
+
+    #[test]
+    fn is_primitive_root_test() {
+        assert_eq!(ModInt::new(3, 7).is_primitive_root(), Some(true));
+        assert_eq!(ModInt::new(2, 7).is_primitive_root(), Some(false));
+        assert_eq!(ModInt::new(3, 998_244_353).is_primitive_root(), Some(true));
+        assert_eq!(
+            ModInt::new(5, 1_000_000_007).is_primitive_root(),
+            Some(true)
+        );
+        assert_eq!(ModInt::new(1, 2).is_primitive_root(), Some(true));
+
+        // mod 13 の原始根は φ(12) = 4 個
+        let cnt = (1..13)
+            .filter(|&g| ModInt::new(g, 13).is_primitive_root().unwrap())
+            .count();
+        assert_eq!(cnt, 4);
+
+        assert_eq!(ModInt::new(0, 7).is_primitive_root(), None);
+        assert_eq!(ModInt::new(5, 12).is_primitive_root(), None);
+    }
+
+    #[test]
+    fn pow_bigexp_test() {
+        // 指数の桁ごとに (x^e)^10 * x^d で組み立てる参照解
+        let reference = |x: ModInt, e: &str| {
+            e.chars().fold(ModInt::new(1, x.get_mod()), |acc, c| {
+                acc.pow(10) * x.pow(c.to_digit(10).unwrap() as usize)
+            })
+        };
+        let e = "1234567890".repeat(10);
+        assert_eq!(e.len(), 100);
+        for &(a, m) in &[
+            (3, 1_000_000_007),
+            (2, 12),
+            (6, 1 << 20),
+            (0, 7),
+            (10, 1000),
+            (5, 1),
+        ] {
+            let x = ModInt::new(a, m);
+            assert_eq!(x.pow_bigexp(&e), reference(x, &e));
+        }
+
+        // 小さい指数では一般化オイラーの条件を満たさないので直接
+        let x = ModInt::new(2, 16);
+        assert_eq!(x.pow_bigexp("0").get(), 1);
+        assert_eq!(x.pow_bigexp("3").get(), 8);
+        assert_eq!(x.pow_bigexp("4").get(), 0);
+        assert_eq!(ModInt::new(0, 7).pow_bigexp("0").get(), 1);
+    }
+
+    #[test]
+    fn powers_test() {
+        let a = ModInt::new(3, 7);
+        let p = a.powers().take(5).collect::<Vec<_>>();
+        for (k, e) in p.into_iter().enumerate() {
+            assert_eq!(e, a.pow(k));
+        }
+        // 最初の1も素数modであることを覚えている
+        let b = ModInt::new_prime(3, 1_000_000_007);
+        assert!(b.powers().next().unwrap().is_prime_modulus_known());
+        assert!(!a.powers().next().unwrap().is_prime_modulus_known());
+    }
+
+    #[test]
+    fn mul_root_power_test() {
+        let m = 998_244_353;
+        let table = crate::ntt::RootTable::new(m, 4);
+        let w = table.forward[1];
+        let x = ModInt::new(123_456_789, m);
+        let n = table.forward.len();
+        for k in 0..40 {
+            assert_eq!(x.mul_root_power(&table.forward, k % n), x * w.pow(k));
+            assert_eq!(
+                x.mul_root_power(&table.inverse, k % n),
+                x * w.checked_inv().unwrap().pow(k)
+            );
+        }
+        assert!(std::panic::catch_unwind(|| x.mul_root_power(&table.forward, n)).is_err());
+    }
+
+    #[test]
+    fn square_test() {
+        for &m in &[7u32, 1_000_000_007, 4_294_967_291] {
+            for &x in &[0i64, 1, 2, -1, -2, m as i64 / 2, 123_456_789] {
+                let x = ModInt::new(x, m);
+                assert_eq!(x.square(), x * x);
+            }
+        }
+        assert!(ModInt::new_prime(5, 7).square().is_prime_modulus_known());
+    }
+
+    #[test]
+    fn mint_new() {
+        let m = ModInt::new(10, 3);
+        assert_eq!(m.get(), 1);
+
+        let m = ModInt::new(-10, 3);
+        assert_eq!(m.get(), 2);
+
+        let x = 4.to_mint(10); // this is also valid
+        let y = ModInt::new(4, 10);
+        assert_eq!(x, y);
+    }
+
+    #[test]
+    fn eq_dynamic_unreduced_test() {
+        // Dynamicで30のまま、mod 7では2
+        let d = ModInt::from_str_radix("30", 10).unwrap();
+        let s = ModInt::new(2, 7);
+        assert_eq!(d, s);
+        assert_eq!(s, d);
+        assert_ne!(d, ModInt::new(30, 31) - 1);
+        assert_eq!(d.partial_cmp(&s), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn try_into_usize_test() {
+        assert_eq!(ModInt::new(-3, 7).try_into_usize(), Ok(4));
+        let broken = ModInt {
+            num: -1,
+            _modulo: Modulo::Dynamic,
+        };
+        assert!(broken.try_into_usize().is_err());
+    }
+
+    #[test]
+    fn signed_residue_test() {
+        let m = 1_000_000_007;
+        for &x in &[0i64, 1, -1, 12_345, -12_345, 500_000_003, -500_000_003] {
+            let r = ModInt::new(x, m).signed_residue();
+            assert_eq!(r, x);
+            assert_eq!(r.signum(), x.signum());
+        }
+        // 0に近い方へ
+        assert_eq!(ModInt::new(500_000_004, m).signed_residue(), -500_000_003);
+        assert_eq!(ModInt::new(6, 10).signed_residue(), -4);
+        assert_eq!(ModInt::new(5, 10).signed_residue(), 5);
+        for x in -3..=3 {
+            assert_eq!(ModInt::new(x, 7).signed_residue(), x);
+        }
+    }
+
+    #[test]
+    fn representative_test() {
+        use Representative::*;
+
+        let m = 7;
+        let v = (0..7)
+            .map(|i| ModInt::new(i, m).representative(LeastAbsolute))
+            .collect::<Vec<_>>();
+        assert_eq!(v, vec![0, 1, 2, 3, -3, -2, -1]);
+        for i in 0..7 {
+            assert_eq!(ModInt::new(i, m).representative(LeastNonNegative), i);
+        }
+
+        // 偶数のmod
+        let m = 10;
+        assert_eq!(ModInt::new(4, m).representative(LeastAbsolute), 4);
+        assert_eq!(ModInt::new(5, m).representative(LeastAbsolute), 5);
+        assert_eq!(ModInt::new(6, m).representative(LeastAbsolute), -4);
+        assert_eq!(
+            ModInt::new(-1, 1_000_000_007).representative(LeastAbsolute),
+            -1
+        );
+    }
+
+    #[test]
+    fn try_get_mod_test() {
+        assert_eq!(ModInt::new(3, 7).try_get_mod(), Some(7));
+        assert_eq!(ModInt::zero().try_get_mod(), None);
+        assert_eq!(ModInt::one().try_get_mod(), None);
+        assert_eq!("12".parse::<ModInt>().unwrap().try_get_mod(), None);
+    }
+
+    #[test]
+    fn new_prime_test() {
+        let p = 1_000_000_007;
+        let a = ModInt::new_prime(6, p);
+        let b = ModInt::new(6, p);
+        assert!(a.is_prime_modulus_known());
+        assert!(!b.is_prime_modulus_known());
+        assert_eq!(a, b);
+        assert_eq!(b, a);
+        assert_eq!(ModInt::new_prime(-1, p).get(), p as i64 - 1);
+
+        // 素数の印は演算で引き継がれる
+        assert!((a + b).is_prime_modulus_known());
+        assert!((b * a).is_prime_modulus_known());
+        assert!((a + 1i64).is_prime_modulus_known());
+        assert!(!(b + b).is_prime_modulus_known());
+
+        // フェルマーの小定理による逆元
+        let inv = a.checked_inv().unwrap();
+        assert!(inv.is_prime_modulus_known());
+        assert_eq!(inv, b.checked_inv().unwrap());
+        assert_eq!(a.inv(), b.inv());
+        assert_eq!(a / b, ModInt::new(1, p));
+        assert!(ModInt::new_prime(0, p).checked_inv().is_none());
+        assert_eq!(a.is_quadratic_residue(), b.is_quadratic_residue());
+
+        assert!(std::panic::catch_unwind(|| ModInt::new_prime(1, 7) + ModInt::new(1, 11)).is_err());
+    }
+
+    #[test]
+    fn from_bool_test() {
+        assert_eq!(ModInt::from_bool(true, 7), ModInt::new(1, 7));
+        assert_eq!(ModInt::from_bool(false, 7), ModInt::new(0, 7));
+        assert_eq!(ModInt::from_bool(true, 1).get(), 0);
+    }
+
+    #[test]
+    fn equals_int_test() {
+        let a = ModInt::new(2, 10);
+        assert!(a.equals_int(12));
+        assert!(a.equals_int(-8));
+        assert!(a.equals_int(2));
+        assert!(!a.equals_int(3));
+        assert!(ModInt::new(0, 7).equals_int(std::i64::MIN + 1 - (std::i64::MIN + 1) % 7));
+        assert!(ModInt::new(-1, 1_000_000_007).equals_int(-1));
+    }
+
+    #[test]
+    fn from_ascii_digit_test() {
+        for (i, c) in (b'0'..=b'9').enumerate() {
+            assert_eq!(
+                ModInt::from_ascii_digit(c, 1_000_000_007).unwrap().get(),
+                i as i64
+            );
+        }
+        assert_eq!(ModInt::from_ascii_digit(b'9', 7), Some(ModInt::new(2, 7)));
+        for &c in b"a/:- " {
+            assert_eq!(ModInt::from_ascii_digit(c, 7), None);
+        }
+        // 桁ごとに読む
+        let x = b"31415926535".iter().fold(ModInt::new(0, 13), |acc, &c| {
+            acc * 10 + ModInt::from_ascii_digit(c, 13).unwrap()
+        });
+        assert_eq!(x, ModInt::new(31_415_926_535i64, 13));
+    }
+
+    #[test]
+    fn from_fraction_test() {
+        assert_eq!(ModInt::from_fraction(1, 3, 13), ModInt::new(1, 13) / 3);
+        assert_eq!(ModInt::from_fraction(1, 3, 13) * 3, ModInt::new(1, 13));
+        assert_eq!(ModInt::from_fraction(-5, 10, 13), ModInt::new(-1, 13) / 2);
+        assert_eq!(
+            ModInt::from_fraction(7, -7, 1_000_000_007).get(),
+            1_000_000_006
+        );
+        assert!(std::panic::catch_unwind(|| ModInt::from_fraction(1, 26, 13)).is_err());
+        assert!(std::panic::catch_unwind(|| ModInt::from_fraction(1, 4, 12)).is_err());
+    }
+
+    #[test]
+    fn from_f64_test() {
+        let m = 1_000_000_007;
+        assert_eq!(ModInt::from_f64(42.0, m), Some(ModInt::new(42, m)));
+        assert_eq!(ModInt::from_f64(-3.0, m), Some(ModInt::new(-3, m)));
+        assert_eq!(
+            ModInt::from_f64(1e15, m),
+            Some(ModInt::new(1_000_000_000_000_000i64, m))
+        );
+        // 誤差の範囲内
+        assert_eq!(
+            ModInt::from_f64(0.1 + 0.2 - 0.3 + 7.0, m),
+            Some(ModInt::new(7, m))
+        );
+        assert_eq!(
+            ModInt::from_f64(5.000_000_000_1, m),
+            Some(ModInt::new(5, m))
+        );
+        // 整数でない
+        assert_eq!(ModInt::from_f64(2.5, m), None);
+        assert_eq!(ModInt::from_f64(1.001, m), None);
+        assert_eq!(ModInt::from_f64(std::f64::NAN, m), None);
+        assert_eq!(ModInt::from_f64(std::f64::INFINITY, m), None);
+        assert_eq!(ModInt::from_f64(1e19, m), None);
+    }
+
+    #[test]
+    fn inv_test() {
+        let a = ModInt::new(6, 13);
+        assert_eq!(a.inv(), 11);
+    }
+
+    #[test]
+    fn checked_inv_test() {
+        assert_eq!(ModInt::new(6, 13).checked_inv(), Some(ModInt::new(11, 13)));
+        assert_eq!(ModInt::new(4, 12).checked_inv(), None);
+        assert_eq!(ModInt::new(5, 12).checked_inv(), Some(ModInt::new(5, 12)));
+    }
+
+    #[test]
+    fn inv_checked_prime_test() {
+        let p = 1_000_000_007;
+        for x in 1..50 {
+            let a = ModInt::new(x * 1_234_567, p);
+            assert_eq!(a.inv_checked_prime(), a.checked_inv());
+        }
+        assert_eq!(ModInt::new(0, p).inv_checked_prime(), None);
+        // 合成数
+        for x in 0..12 {
+            let a = ModInt::new(x, 12);
+            assert_eq!(a.inv_checked_prime(), a.checked_inv());
+        }
+        assert_eq!(
+            ModInt::new(5, 12).inv_checked_prime(),
+            Some(ModInt::new(5, 12))
+        );
+        assert_eq!(ModInt::new(4, 12).inv_checked_prime(), None);
+        assert_eq!(
+            ModInt::new(3, 7).inv_checked_prime(),
+            Some(ModInt::new(5, 7))
+        );
+    }
+
+    #[test]
+    fn inv_pow2_test() {
+        let m = 1 << 16;
+        for a in (1..2000).step_by(2) {
+            let a = ModInt::new(a * 37, m);
+            let inv = a.inv_pow2().unwrap();
+            assert_eq!(Some(inv), a.checked_inv());
+            assert!((a * inv).is_one());
+        }
+        assert_eq!(ModInt::new(4, m).inv_pow2(), None);
+        assert_eq!(ModInt::new(0, m).inv_pow2(), None);
+        assert_eq!(ModInt::new(3, 12).inv_pow2(), None);
+        let big = 1u32 << 31;
+        let a = ModInt::new(big - 1, big);
+        assert_eq!(a.inv_pow2(), Some(a));
+        assert_eq!(
+            ModInt::new(0, 1).inv_pow2(),
+            ModInt::new(0, 1).checked_inv()
+        );
+    }
+
+    #[test]
+    fn try_div_test() {
+        let a = ModInt::new(3, 13);
+        assert_eq!(a.try_div(ModInt::new(6, 13)), Some(ModInt::new(3, 13) / 6));
+        assert_eq!(a.try_div(ModInt::new(0, 13)), None);
+        assert_eq!(ModInt::new(5, 12).try_div(ModInt::new(4, 12)), None);
+        assert_eq!(
+            ModInt::new(5, 12).try_div(ModInt::new(5, 12)),
+            Some(ModInt::new(1, 12))
+        );
+        // `Dynamic`な側は相手のmodで扱う
+        assert_eq!(a.try_div(ModInt::one()), Some(a));
+    }
+
+    #[test]
+    fn and_then_mod_test() {
+        let m = 13;
+        let a = ModInt::new(3, m);
+        let r = a
+            .and_then_mod(|x| x.try_div(ModInt::new(6, m)))
+            .and_then(|x| x.zip_mod(ModInt::new(4, m)))
+            .map(|(x, y)| x + y);
+        assert_eq!(r, Some(ModInt::new(3, m) / 6 + 4));
+        // 途中で割れなくなる
+        let r = a
+            .and_then_mod(|x| x.try_div(ModInt::new(0, m)))
+            .map(|x| x + 1);
+        assert_eq!(r, None);
+
+        assert_eq!(a.zip_mod(ModInt::new(1, 7)), None);
+        assert_eq!(a.zip_mod(ModInt::one()), Some((a, ModInt::new(1, m))));
+        assert!(ModInt::one().zip_mod(ModInt::zero()).is_none());
+    }
+
+    #[test]
+    fn div_reporting_test() {
+        let m = 12;
+        let (q, ok) = ModInt::new(10, m).div_reporting(ModInt::new(5, m));
+        assert!(ok);
+        assert_eq!(q * 5, ModInt::new(10, m));
+        let (q, ok) = ModInt::new(10, m).div_reporting(ModInt::new(4, m));
+        assert!(!ok);
+        assert_eq!(q, ModInt::new(0, m));
+        let (q, ok) = ModInt::new(3, 13).div_reporting(ModInt::new(0, 13));
+        assert_eq!((q.get(), ok), (0, false));
+        assert_eq!(q.get_mod(), 13);
+    }
+
+    #[test]
+    fn div_rem_integer_test() {
+        let m = 13;
+        let a = ModInt::new(10, m);
+        let (q, r) = a.div_rem_integer(3);
+        assert_eq!(q, 3);
+        assert_eq!(r * 3, a);
+        assert_eq!(r, ModInt::new(12, m));
+
+        let (q, r) = a.div_rem_integer(-4);
+        assert_eq!(q, -2);
+        assert_eq!(r * -4, a);
+
+        let (q, r) = ModInt::new(6, 10).div_rem_integer(7);
+        assert_eq!(q, 0);
+        assert_eq!(r, ModInt::new(8, 10));
+        assert!(std::panic::catch_unwind(|| ModInt::new(6, 10).div_rem_integer(2)).is_err());
+        assert!(std::panic::catch_unwind(|| a.div_rem_integer(0)).is_err());
+    }
+
+    impl Add<Self> for ModInt {
+        type Output = Self;
+        fn add(self, rhs: Self) -> Self::Output {
+            let (lhs, rhs) = unify(self, rhs);
+            let m = lhs.get_mod() as i64;
+            let r = lhs.get() + rhs.get();
+            Self {
+                num: if r >= m { r - m } else { r },
+                _modulo: lhs._modulo,
+            }
+        }
+    }
+
+    impl AddAssign<Self> for ModInt {
+        fn add_assign(&mut self, rhs: Self) {
+            *self = *self + rhs;
+        }
+    }
+
+    #[test]
+    fn mint_add() {
+        let a = ModInt::new(13, 8); // 5
+        let b = ModInt::new(10, 8); // 2
+        assert_eq!((a + b).get(), 7);
+
+        let c = ModInt::new(7, 8); //7
+        assert_eq!((a + c).get(), 4); // (5 + 7) % 8 == 4
+    }
+
+    impl Sub<Self> for ModInt {
+        type Output = Self;
+        fn sub(self, rhs: Self) -> Self::Output {
+            let (lhs, rhs) = unify(self, rhs);
+            let num = compensated_rem(lhs.get() - rhs.get(), lhs.get_mod());
+            Self {
+                num,
+                _modulo: lhs._modulo,
+            }
+        }
+    }
+
+    impl SubAssign<Self> for ModInt {
+        fn sub_assign(&mut self, rhs: Self) {
+            *self = *self - rhs;
+        }
+    }
+
+    #[test]
+    fn mint_sub() {
+        let a = ModInt::new(2, 10);
+        let b = ModInt::new(3, 10);
+
+        assert_eq!((b - a).get(), 1);
+        assert_eq!((a - b).get(), 9);
+    }
+
+    impl Neg for ModInt {
+        type Output = Self;
+        /// # Panic
+        /// `Dynamic`で0でない時
+        fn neg(self) -> Self::Output {
+            if self.get() == 0 {
+                return self;
+            }
+            Self {
+                num: self.get_mod() as i64 - self.get(),
+                _modulo: self._modulo,
+            }
+        }
+    }
+
+    #[test]
+    fn neg_test() {
+        assert_eq!((-ModInt::new(3, 7)).get(), 4);
+        assert_eq!((-ModInt::new(0, 7)).get(), 0);
+        assert_eq!(-(-ModInt::new(5, 7)), ModInt::new(5, 7));
+        assert!((-ModInt::zero()).is_zero());
+    }
+
+    impl Mul<Self> for ModInt {
+        type Output = Self;
+        fn mul(self, rhs: Self) -> Self::Output {
+            let (lhs, rhs) = unify(self, rhs);
+            let num = mul_mod(lhs.get(), rhs.get(), lhs.get_mod());
+            Self {
+                num,
+                _modulo: lhs._modulo,
+            }
+        }
+    }
+
+    impl MulAssign<Self> for ModInt {
+        fn mul_assign(&mut self, rhs: Self) {
+            *self = *self * rhs
+        }
+    }
+
+    /// # Panic
+    /// `rhs`がmodと互いに素でない(逆元がない)時
+    impl Div<Self> for ModInt {
+        type Output = Self;
+        // 逆元を掛けるのが除算
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        fn div(self, rhs: Self) -> Self::Output {
+            let (lhs, rhs) = unify(self, rhs);
+            lhs * rhs.checked_inv().expect("divisor is not invertible")
+        }
+    }
+
+    impl DivAssign<Self> for ModInt {
+        fn div_assign(&mut self, rhs: Self) {
+            *self = *self / rhs;
+        }
+    }
 
     #[test]
     fn div_test() {
@@ -329,6 +1458,11 @@ pub mod modint {
         let b = ModInt::new(3, 5);
         assert_eq!(a / b, ModInt::new(4, 5));
 
+        // 逆元がなければModInt同士でもプリミティブでも同じくpanic
+        assert!(std::panic::catch_unwind(|| ModInt::new(3, 12) / ModInt::new(4, 12)).is_err());
+        assert!(std::panic::catch_unwind(|| ModInt::new(3, 12) / 4i64).is_err());
+        assert_eq!((ModInt::new(3, 12) / ModInt::new(5, 12)).get(), 3);
+
         let x = ModInt::new(1, 13);
         assert_eq!((x / 4i64).get(), 10);
 
@@ -366,16 +1500,52 @@ pub mod modint {
         assert_eq!((x / 4i64).get(), 3);
     }
 
+    macro_rules! impl_assign_by_ref {
+        ($($tr:ident, $f:ident);*) => {
+            $(
+                impl<'a> $tr<&'a ModInt> for ModInt {
+                    fn $f(&mut self, rhs: &'a ModInt) {
+                        self.$f(*rhs);
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_assign_by_ref!(
+        AddAssign, add_assign;
+        SubAssign, sub_assign;
+        MulAssign, mul_assign;
+        DivAssign, div_assign
+    );
+
+    #[test]
+    fn assign_by_ref_test() {
+        let v = vec![ModInt::new(3, 7), ModInt::new(5, 7), ModInt::new(6, 7)];
+        let mut acc = ModInt::new(0, 7);
+        for e in &v {
+            acc += e;
+        }
+        assert_eq!(acc.get(), 0);
+
+        let mut acc = ModInt::new(1, 7);
+        for e in v.iter() {
+            acc *= e;
+        }
+        assert_eq!(acc.get(), 6);
+        acc -= &v[0];
+        assert_eq!(acc.get(), 3);
+        acc /= &v[1];
+        assert_eq!(acc * v[1], ModInt::new(3, 7));
+    }
+
     impl Rem for ModInt {
         type Output = Self;
         fn rem(self, rhs: Self) -> Self::Output {
-            let c = check_mod_eq(&self, &rhs);
-            if !c.1 {
-                panic!("modulo between two instance is different!",)
-            }
+            let (lhs, rhs) = unify(self, rhs);
             Self {
-                num: self.num % rhs.num,
-                _modulo: Modulo::Static(c.0),
+                num: lhs.num % rhs.num,
+                _modulo: lhs._modulo,
             }
         }
     }
@@ -386,12 +1556,39 @@ pub mod modint {
         }
     }
 
+    impl ModInt {
+        /// `const`な文脈で使える0、`zero()`と同じく`Dynamic`
+        ///
+        /// `num_traits::ConstZero`は固定しているnum-traits(0.2.11)にはまだないので、
+        /// 関連定数として用意する
+        pub const ZERO: ModInt = ModInt {
+            num: 0,
+            _modulo: Modulo::Dynamic,
+        };
+
+        /// `const`な文脈で使える1、`one()`と同じく`Dynamic`
+        pub const ONE: ModInt = ModInt {
+            num: 1,
+            _modulo: Modulo::Dynamic,
+        };
+    }
+
+    #[test]
+    fn const_identity_test() {
+        const Z: ModInt = ModInt::ZERO;
+        const O: [ModInt; 2] = [ModInt::ONE; 2];
+        assert!(Z.is_zero());
+        assert!(O[1].is_one());
+        let a = ModInt::new(5, 7);
+        assert_eq!(a + Z, a);
+        assert_eq!(a * O[0], a);
+        assert_eq!(Z.get(), ModInt::zero().get());
+        assert!(Z.try_get_mod().is_none());
+    }
+
     impl Zero for ModInt {
         fn zero() -> Self {
-            ModInt {
-                num: 0,
-                _modulo: Modulo::Dynamic,
-            }
+            Self::ZERO
         }
         fn is_zero(&self) -> bool {
             self.num == 0
@@ -400,10 +1597,7 @@ pub mod modint {
 
     impl One for ModInt {
         fn one() -> Self {
-            ModInt {
-                num: 1,
-                _modulo: Modulo::Dynamic,
-            }
+            Self::ONE
         }
         fn is_one(&self) -> bool {
             self.num == 1
@@ -426,18 +1620,144 @@ pub mod modint {
         }
     }
 
-    impl Pow<usize> for ModInt {
-        type Output = Self;
-        // fn pow(self, exp: u32) -> Self::Output {
-        //     if exp == 0 {
-        //         return T::one();
-        //     }
+    /// 数字の間の`_`を取り除く (`"1_000_000_007"`)
+    ///
+    /// 空の時、数字と`_`以外を含む時、`_`が先頭・末尾にある、または連続している時は`None`
+    fn strip_digit_separators(digits: &str) -> Option<String> {
+        let b = digits.as_bytes();
+        let ok = !b.is_empty()
+            && b.iter().all(|&c| c.is_ascii_digit() || c == b'_')
+            && b[0] != b'_'
+            && b[b.len() - 1] != b'_'
+            && !digits.contains("__");
+        if ok {
+            Some(digits.replace('_', ""))
+        } else {
+            None
+        }
+    }
 
-        //     while exp & 1 == 0 {
-        //         base = base.clone() * base;
-        //         exp >>= 1;
-        //     }
-        //     if exp == 1 {
+    /// 符号付き10進数のトークンを読んで`modulus`で割った余りにする
+    ///
+    /// 先頭の`+`/`-`を受け付ける、桁ごとに余りをとるので`i64`に収まらなくても良い
+    ///
+    /// 数字の間の`_`は読み飛ばす、先頭・末尾や連続した`_`は弾く
+    ///
+    /// 数値でないトークンは`ParseIntError`
+    pub fn parse_mod(token: &str, modulus: u32) -> Result<ModInt, ParseIntError> {
+        let (negative, digits) = match token.as_bytes().first() {
+            Some(b'-') => (true, &token[1..]),
+            Some(b'+') => (false, &token[1..]),
+            _ => (false, token),
+        };
+        let digits = match strip_digit_separators(digits) {
+            Some(d) => d,
+            // このときi64としてのparseも必ず失敗するので、そのエラーを返す
+            None => return Err(token.parse::<i64>().unwrap_err()),
+        };
+        let abs = digits
+            .bytes()
+            .fold(ModInt::new(0, modulus), |acc, c| acc * 10 + (c - b'0'));
+        Ok(if negative {
+            ModInt::new(0, modulus) - abs
+        } else {
+            abs
+        })
+    }
+
+    #[test]
+    fn parse_mod_test() {
+        assert_eq!(parse_mod("-5", 7).unwrap().get(), 2);
+        assert_eq!(parse_mod("+12", 7).unwrap().get(), 5);
+        assert_eq!(parse_mod("0", 7).unwrap().get(), 0);
+        assert_eq!(
+            parse_mod("123456789012345678901234567890", 1_000_000_007)
+                .unwrap()
+                .get(),
+            (123_456_789_012_345_678_901_234_567_890u128 % 1_000_000_007) as i64
+        );
+
+        assert!(parse_mod("abc", 7).is_err());
+        assert!(parse_mod("12a", 7).is_err());
+        assert!(parse_mod("", 7).is_err());
+        assert!(parse_mod("-", 7).is_err());
+        assert!(parse_mod("--5", 7).is_err());
+    }
+
+    #[test]
+    fn parse_with_separators_test() {
+        assert_eq!(parse_mod("1_000", 7).unwrap(), ModInt::new(1000, 7));
+        assert_eq!(
+            parse_mod("-1_000_000_007", 13).unwrap(),
+            ModInt::new(-1_000_000_007, 13)
+        );
+        assert_eq!(parse_mod("1_000_000_007", 1_000_000_007).unwrap().get(), 0);
+        for bad in &["1__0", "_1", "1_", "-_1", "_", "1_a", "1 _0"] {
+            assert!(parse_mod(bad, 7).is_err(), "{}", bad);
+            assert!(bad.parse::<ModInt>().is_err(), "{}", bad);
+        }
+
+        assert_eq!("1_000".parse::<ModInt>().unwrap().get(), 1000);
+        assert_eq!("998_244_353".parse::<ModInt>().unwrap().get(), 998_244_353);
+        assert!("-1_0".parse::<ModInt>().is_err());
+    }
+
+    /// 10進数の文字列から`Modulo::Dynamic`な値を作る
+    ///
+    /// 相手となるmodがまだないので割った余りはとらない、
+    /// `Static`な値と演算したときにはじめてそのmodで割られる
+    ///
+    /// 数字以外を含むときは`Err`、`_`は`parse_mod`と同じ規則で読み飛ばす
+    impl FromStr for ModInt {
+        type Err = ParseIntError;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            if s.starts_with('+') || s.starts_with('-') {
+                // `i64`の読み込みは符号を受け付けるので、先に数字以外として弾く
+                return Err("_".parse::<u64>().unwrap_err());
+            }
+            let num = match strip_digit_separators(s) {
+                Some(d) => d.parse::<i64>()?,
+                None => s.parse::<i64>()?,
+            };
+            Ok(ModInt {
+                num,
+                _modulo: Modulo::Dynamic,
+            })
+        }
+    }
+
+    #[test]
+    fn from_str_test() {
+        let a = "5".parse::<ModInt>().unwrap();
+        assert_eq!(a + ModInt::new(3, 7), ModInt::new(1, 7));
+
+        // Staticな値と演算するまでは割られない
+        let b = "100".parse::<ModInt>().unwrap();
+        assert_eq!(b.get(), 100);
+        assert_eq!((b + ModInt::new(3, 7)).get(), 5);
+        assert_eq!((ModInt::new(3, 7) * b).get(), 6);
+
+        assert!("".parse::<ModInt>().is_err());
+        assert!("12a".parse::<ModInt>().is_err());
+        assert!("-5".parse::<ModInt>().is_err());
+        assert!("+5".parse::<ModInt>().is_err());
+        assert!("-0".parse::<ModInt>().is_err());
+        assert!("+".parse::<ModInt>().is_err());
+        assert_eq!("0".parse::<ModInt>().unwrap().get(), 0);
+    }
+
+    impl Pow<usize> for ModInt {
+        type Output = Self;
+        // fn pow(self, exp: u32) -> Self::Output {
+        //     if exp == 0 {
+        //         return T::one();
+        //     }
+
+        //     while exp & 1 == 0 {
+        //         base = base.clone() * base;
+        //         exp >>= 1;
+        //     }
+        //     if exp == 1 {
         //         return base;
         //     }
 
@@ -457,6 +1777,20 @@ pub mod modint {
         }
     }
 
+    impl<'a> Pow<&'a usize> for ModInt {
+        type Output = Self;
+        fn pow(self, exp: &'a usize) -> Self::Output {
+            self.pow(*exp)
+        }
+    }
+
+    impl<'a> Pow<&'a u64> for ModInt {
+        type Output = Self;
+        fn pow(self, exp: &'a u64) -> Self::Output {
+            self.pow(*exp as usize)
+        }
+    }
+
     #[test]
     fn pow_test() {
         let a = ModInt::new(3, 10);
@@ -466,13 +1800,118 @@ pub mod modint {
         assert_eq!(b.pow(2).get(), 1);
     }
 
+    #[test]
+    fn pow_by_ref_test() {
+        let a = ModInt::new(3, 10);
+        assert_eq!(a.pow(&3usize).get(), 7);
+        assert_eq!(a.pow(&3u64).get(), 7);
+
+        // 参照の指数を要求するジェネリックなコードから
+        assert_eq!(<ModInt as Pow<&usize>>::pow(a, &4), a.pow(4));
+    }
+
+    /// 列の全要素を同じ指数`exp`で累乗する
+    ///
+    /// 指数のビット列は一度だけ求めて、各要素で使い回す
+    ///
+    /// `O(n log exp)`
+    pub fn pow_each(values: &[ModInt], exp: u64) -> Vec<ModInt> {
+        // 上位ビットから
+        let bits = (0..64 - exp.leading_zeros())
+            .rev()
+            .map(|i| exp >> i & 1 == 1)
+            .collect::<Vec<_>>();
+        values
+            .iter()
+            .map(|&v| {
+                let mut acc = v.lift(1);
+                for &b in &bits {
+                    acc = acc.square();
+                    if b {
+                        acc *= v;
+                    }
+                }
+                acc
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pow_each_test() {
+        let m = 998_244_353;
+        let v = (0..20)
+            .map(|i| ModInt::new(i * 31_415_926 - 7, m))
+            .collect::<Vec<_>>();
+        for &exp in &[0u64, 1, 2, 3, 10, 998_244_352, 1 << 40, std::u64::MAX] {
+            let each = pow_each(&v, exp);
+            assert_eq!(each.len(), v.len());
+            for (x, y) in v.iter().zip(each.iter()) {
+                assert_eq!(x.pow(&exp), *y);
+            }
+        }
+        assert!(pow_each(&[], 5).is_empty());
+    }
+
+    impl ModInt {
+        /// `self + 1`、modを保ったまま、`m - 1`の次は0
+        ///
+        /// `Dynamic`な値はそのまま1を足す
+        pub fn increment(self) -> Self {
+            let num = match self._modulo.get() {
+                Some(m) if self.num + 1 == m as i64 => 0,
+                _ => self.num + 1,
+            };
+            Self { num, ..self }
+        }
+
+        /// `self - 1`、modを保ったまま、0の前は`m - 1`
+        ///
+        /// `Dynamic`な値はそのまま1を引く
+        pub fn decrement(self) -> Self {
+            let num = match self._modulo.get() {
+                Some(m) if self.num == 0 => m as i64 - 1,
+                _ => self.num - 1,
+            };
+            Self { num, ..self }
+        }
+    }
+
+    #[test]
+    fn increment_decrement_test() {
+        let m = 7;
+        assert_eq!(ModInt::new(6, m).increment(), ModInt::new(0, m));
+        assert_eq!(ModInt::new(0, m).decrement(), ModInt::new(6, m));
+        assert_eq!(ModInt::new(3, m).increment().get(), 4);
+        assert_eq!(ModInt::new(3, m).decrement().get(), 2);
+        let mut x = ModInt::new(5, m);
+        for i in 1..=20 {
+            x = x.increment();
+            assert_eq!(x, ModInt::new(5 + i, m));
+        }
+        for i in 1..=20 {
+            x = x.decrement();
+            assert_eq!(x, ModInt::new(25 - i, m));
+        }
+        // mod 1 では常に0
+        assert_eq!(ModInt::new(0, 1).increment().get(), 0);
+        assert_eq!(ModInt::new(0, 1).decrement().get(), 0);
+        assert!(ModInt::new_prime(6, 7).increment().is_prime_modulus_known());
+        assert_eq!(ModInt::one().increment().get(), 2);
+
+        // 下降階乗・上昇階乗
+        assert_eq!(ModInt::new(5, 1_000_000_007).falling(3).get(), 60);
+        assert_eq!(ModInt::new(5, 1_000_000_007).rising(3).get(), 210);
+        assert_eq!(ModInt::new(1, 7).falling(3).get(), 0);
+        assert_eq!(ModInt::new(6, 7).rising(2).get(), 0);
+    }
+
     impl Factoriable for ModInt {
         fn falling(self, take: usize) -> Self {
             let mut res = Self::one();
             let mut c = self;
             for _ in 0..take {
                 res *= c;
-                c -= Self::one();
+                c = c.decrement();
             }
             res
         }
@@ -481,10 +1920,13 @@ pub mod modint {
             let mut c = self;
             for _ in 0..take {
                 res *= c;
-                c += 1;
+                c = c.increment();
             }
             res
         }
+        fn one_like(self) -> Self {
+            self.lift(1)
+        }
     }
 
     // #[test]
@@ -577,13 +2019,108 @@ pub mod modint {
     //     }
     // }
 
+    /// 剰余の値そのもの(`get()`)どうしの最大公約数
+    ///
+    /// 環の演算ではないので結果は`ModInt`ではなく整数で返す
+    ///
+    /// # Panic
+    /// 異なるmod間での演算をattemptした時
+    pub fn residue_gcd(a: ModInt, b: ModInt) -> i64 {
+        if !check_mod_eq(&a, &b).1 {
+            modulo_mismatch(&a, &b)
+        }
+        a.get().gcd(&b.get())
+    }
+
+    /// 剰余の値そのもの(`get()`)どうしの最小公倍数
+    ///
+    /// 環の演算ではないので結果は`ModInt`ではなく整数で返す
+    ///
+    /// # Panic
+    /// 異なるmod間での演算をattemptした時
+    pub fn residue_lcm(a: ModInt, b: ModInt) -> i64 {
+        if !check_mod_eq(&a, &b).1 {
+            modulo_mismatch(&a, &b)
+        }
+        a.get().lcm(&b.get())
+    }
+
+    #[test]
+    fn residue_gcd_lcm_test() {
+        let a = ModInt::new(12, 100);
+        let b = ModInt::new(118, 100); // 18
+        assert_eq!(residue_gcd(a, b), 6);
+        assert_eq!(residue_lcm(a, b), 36);
+
+        let c = ModInt::new(7, 100);
+        assert_eq!(residue_gcd(a, c), 1);
+        assert_eq!(residue_lcm(a, c), 84);
+
+        let z = ModInt::new(100, 100); // 0
+        assert_eq!(residue_gcd(a, z), 12);
+        assert_eq!(residue_lcm(a, z), 0);
+    }
+
+    impl ModInt {
+        /// プリミティブ整数を`self`と同じmodの`ModInt`にする
+        ///
+        /// すでに`[0, m)`に入っている値は剰余演算を省略する
+        fn lift(&self, rhs: i64) -> Self {
+            let m = self.get_mod();
+            let num = if 0 <= rhs && rhs < m as i64 {
+                rhs
+            } else {
+                compensated_rem(rhs, m)
+            };
+            Self {
+                num,
+                _modulo: self._modulo,
+            }
+        }
+    }
+
+    impl ModInt {
+        /// 符号なしのプリミティブ整数を`self`と同じmodの`ModInt`にする
+        ///
+        /// `i64`に収まらない値でも正しい
+        fn lift_u64(&self, rhs: u64) -> Self {
+            Self {
+                num: (rhs % self.get_mod() as u64) as i64,
+                _modulo: self._modulo,
+            }
+        }
+    }
+
+    #[test]
+    fn lift_boundary_test() {
+        let x = ModInt::new(0, 7);
+        assert_eq!(x.lift(0).get(), 0);
+        assert_eq!(x.lift(6).get(), 6);
+        assert_eq!(x.lift(7).get(), 0);
+        assert_eq!(x.lift(8).get(), 1);
+        assert_eq!(x.lift(-1).get(), 6);
+        assert_eq!(x.lift(-7).get(), 0);
+    }
+
+    #[test]
+    fn small_constant_loop_test() {
+        let m = 998_244_353;
+        let mut x = ModInt::new(0, m);
+        let n = 1_000_000;
+        for _ in 0..n {
+            x += 1;
+        }
+        assert_eq!(x.get(), n % m as i64);
+    }
+
+    // `$lift`でプリミティブを`ModInt`にしてから演算する
     macro_rules! impl_ops_between_mint_and_primitive {
-    ($($t:ty),*) => {
+    ($lift:ident, $via:ty: $($t:ty),*) => {
         $(
             impl Add<$t> for ModInt {
                 type Output = Self;
                 fn add(self, rhs: $t) -> Self::Output {
-                    self + Self::new(rhs as i64, self.get_mod())
+                    self + self.$lift(rhs as $via)
                 }
             }
             impl AddAssign<$t> for ModInt {
@@ -591,21 +2128,10 @@ pub mod modint {
                     *self = *self + rhs;
                 }
             }
-            impl Sub<$t> for ModInt {
-                type Output = Self;
-                fn sub(self, rhs: $t) -> Self::Output {
-                    self - Self::new(rhs as i64, self.get_mod())
-                }
-            }
-            impl SubAssign<$t> for ModInt {
-                fn sub_assign(&mut self, rhs: $t) {
-                    *self = *self - rhs;
-                }
-            }
             impl Mul<$t> for ModInt {
                 type Output = Self;
                 fn mul(self, rhs: $t) -> Self::Output {
-                    self * Self::new(rhs as i64, self.get_mod())
+                    self * self.$lift(rhs as $via)
                 }
             }
             impl MulAssign<$t> for ModInt {
@@ -613,10 +2139,17 @@ pub mod modint {
                     *self = *self * rhs;
                 }
             }
+            /// # Panic
+            /// `rhs`がmodと互いに素でない(逆元がない)時
             impl Div<$t> for ModInt {
                 type Output = Self;
+                #[allow(clippy::suspicious_arithmetic_impl)]
                 fn div(self, rhs: $t) -> Self::Output {
-                    self / Self::new(rhs as i64, self.get_mod())
+                    let inv = self
+                        .$lift(rhs as $via)
+                        .checked_inv()
+                        .expect("divisor is not invertible");
+                    self * inv
                 }
             }
             impl DivAssign<$t> for ModInt {
@@ -628,7 +2161,175 @@ pub mod modint {
     };
 }
 
-    impl_ops_between_mint_and_primitive!(usize, u8, u16, u32, u64, isize, i8, i16, i32, i64);
+    impl_ops_between_mint_and_primitive!(lift_u64, u64: usize, u8, u16, u32, u64);
+    impl_ops_between_mint_and_primitive!(lift, i64: isize, i8, i16, i32, i64);
+
+    // 符号なしは`sub_u64`で一度だけ剰余をとる
+    macro_rules! impl_sub_primitive {
+        (unsigned: $($u:ty),*; signed: $($i:ty),*) => {
+            $(
+                impl Sub<$u> for ModInt {
+                    type Output = Self;
+                    fn sub(self, rhs: $u) -> Self::Output {
+                        self.sub_u64(rhs as u64)
+                    }
+                }
+            )*
+            $(
+                impl Sub<$i> for ModInt {
+                    type Output = Self;
+                    fn sub(self, rhs: $i) -> Self::Output {
+                        self - self.lift(rhs as i64)
+                    }
+                }
+            )*
+            $(
+                impl SubAssign<$u> for ModInt {
+                    fn sub_assign(&mut self, rhs: $u) {
+                        *self = *self - rhs;
+                    }
+                }
+            )*
+            $(
+                impl SubAssign<$i> for ModInt {
+                    fn sub_assign(&mut self, rhs: $i) {
+                        *self = *self - rhs;
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_sub_primitive!(unsigned: usize, u8, u16, u32, u64; signed: isize, i8, i16, i32, i64);
+
+    impl ModInt {
+        /// `self - rhs`
+        ///
+        /// `rhs`の剰余を一度だけとって引くので、`i64`に収まらない`rhs`でも正しい
+        pub fn sub_u64(self, rhs: u64) -> Self {
+            let m = self.get_mod() as u64;
+            let r = (rhs % m) as i64;
+            let d = self.get() - r;
+            Self {
+                num: if d < 0 { d + m as i64 } else { d },
+                _modulo: self._modulo,
+            }
+        }
+
+        /// `self - value`
+        ///
+        /// `value`を先に`compensated_rem`でmodの範囲に直してから引くので、
+        /// `i64::MIN`のような負の大きな値でも正しい
+        pub fn checked_sub_int(&self, value: i64) -> Self {
+            let r = compensated_rem(value, self.get_mod());
+            let d = self.get() - r;
+            Self {
+                num: if d < 0 { d + self.get_mod() as i64 } else { d },
+                _modulo: self._modulo,
+            }
+        }
+    }
+
+    impl ModInt {
+        /// 剰余をとらずに足す
+        ///
+        /// 内側のループで足し続けて最後に一度だけ`finalize`するためのもの
+        /// modの検査もしないので、同じmodの値であることは呼び出し側が保証すること
+        ///
+        /// 結果は`[0, m)`に入っているとは限らない、
+        /// `finalize`するまで他の演算に使ってはいけない
+        ///
+        /// `m < 2^32`なので`2^31`回程度までは`i64`を溢れない
+        pub fn add_unreduced(self, rhs: ModInt) -> ModInt {
+            debug_assert!(self.num >= 0 && rhs.num >= 0);
+            Self {
+                num: self.num + rhs.num,
+                _modulo: self._modulo,
+            }
+        }
+
+        /// `add_unreduced`の後で`[0, m)`に戻す
+        pub fn finalize(self) -> ModInt {
+            Self {
+                num: compensated_rem(self.num, self.get_mod()),
+                _modulo: self._modulo,
+            }
+        }
+    }
+
+    #[test]
+    fn add_unreduced_test() {
+        let m = 998_244_353;
+        let v = (0..1000)
+            .map(|i| ModInt::new(m as i64 - 1 - i * 3, m))
+            .collect::<Vec<_>>();
+        let lazy = v
+            .iter()
+            .fold(ModInt::new(0, m), |acc, &x| acc.add_unreduced(x))
+            .finalize();
+        let eager = v.iter().fold(ModInt::new(0, m), |acc, &x| acc + x);
+        assert_eq!(lazy, eager);
+        assert_eq!(ModInt::new(5, 7).add_unreduced(ModInt::new(6, 7)).get(), 11);
+        assert_eq!(ModInt::new(5, 7).finalize().get(), 5);
+    }
+
+    #[test]
+    fn checked_sub_int_test() {
+        let m = 1_000_000_007;
+        let x = ModInt::new(5, m);
+        let reference = |v: i64| {
+            let r = (5 - v as i128).rem_euclid(m as i128);
+            r as i64
+        };
+        for &v in &[std::i64::MIN, std::i64::MAX, -1, 0, 6, 1 << 62, -(1 << 62)] {
+            assert_eq!(x.checked_sub_int(v).get(), reference(v), "{}", v);
+        }
+        assert_eq!(x.checked_sub_int(5), ModInt::new(0, m));
+        assert_eq!(
+            ModInt::new(3, 4_294_967_291u32)
+                .checked_sub_int(std::i64::MIN)
+                .get(),
+            ((3 - std::i64::MIN as i128).rem_euclid(4_294_967_291)) as i64
+        );
+    }
+
+    #[test]
+    fn sub_u64_test() {
+        let m = 1_000_000_007;
+        let x = ModInt::new(5, m);
+        let big = std::u64::MAX;
+        let expected = (5 + m as u128 - big as u128 % m as u128) % m as u128;
+        assert_eq!(x.sub_u64(big).get(), expected as i64);
+        assert_eq!((x - big).get(), expected as i64);
+        assert_eq!(x.sub_u64(5).get(), 0);
+        assert_eq!(x.sub_u64(6).get(), m as i64 - 1);
+        assert_eq!((x - 6usize).get(), m as i64 - 1);
+        assert_eq!((x - (-1i64)).get(), 6);
+
+        let mut y = ModInt::new(0, 7);
+        y -= 1u64 << 63;
+        assert_eq!(y.get(), (7 - (1u64 << 63) % 7) as i64 % 7);
+    }
+
+    #[test]
+    fn div_primitive_test() {
+        let p = 1_000_000_007;
+        let x = ModInt::new(6, p);
+        assert_eq!(x / -2i64, ModInt::new(-3, p));
+        assert_eq!((x / -2i32) * -2i32, x);
+        assert_eq!(x / -1i8, ModInt::new(-6, p));
+
+        // i64に収まらない大きな符号なし整数
+        let big = std::u64::MAX;
+        let r = (big % p as u64) as i64;
+        assert_eq!(x / big, x / ModInt::new(r, p));
+        assert_eq!((x / big) * big, x);
+
+        // 逆元がない
+        let y = ModInt::new(3, 12);
+        assert!(std::panic::catch_unwind(|| y / 4u32).is_err());
+        assert!(std::panic::catch_unwind(|| y / 0i64).is_err());
+    }
 
     #[test]
     fn op_between_different_type() {
@@ -640,4 +2341,985 @@ pub mod modint {
         mint += 10001;
         assert_eq!(mint.get(), 5);
     }
+
+    /// 二項演算`op`で列を畳み込む
+    ///
+    /// 列が空なら`None`
+    ///
+    /// # Panic
+    /// 異なるmodの要素が混ざっていた時
+    pub fn fold_mod<I: IntoIterator<Item = ModInt>>(
+        iter: I,
+        op: fn(ModInt, ModInt) -> ModInt,
+    ) -> Option<ModInt> {
+        let mut iter = iter.into_iter();
+        let first = iter.next()?;
+        Some(iter.fold(first, |acc, e| {
+            if !check_mod_eq(&acc, &e).1 {
+                modulo_mismatch(&acc, &e)
+            }
+            op(acc, e)
+        }))
+    }
+
+    /// 総積、0の剰余が現れた時点でそれ以降を読まずに0を返す
+    ///
+    /// modは先頭の要素のもの、空なら`Dynamic`な1
+    ///
+    /// # Panic
+    /// 異なるmodの要素が混ざっていた時
+    pub fn product_with_zero_shortcut<I: IntoIterator<Item = ModInt>>(iter: I) -> ModInt {
+        let mut iter = iter.into_iter();
+        let mut acc = match iter.next() {
+            Some(x) => x,
+            None => return ModInt::one(),
+        };
+        while !acc.is_zero() {
+            match iter.next() {
+                Some(x) => acc *= x,
+                None => break,
+            }
+        }
+        acc
+    }
+
+    #[test]
+    fn product_with_zero_shortcut_test() {
+        let m = 1_000_000_007;
+        let v = (1..=10).map(|i| ModInt::new(i, m)).collect::<Vec<_>>();
+        assert_eq!(product_with_zero_shortcut(v.clone()).get(), 3_628_800);
+        assert_eq!(
+            product_with_zero_shortcut(v.clone()),
+            v.iter().cloned().product::<ModInt>()
+        );
+        assert!(product_with_zero_shortcut(vec![]).is_one());
+
+        // 0の後は読まない
+        let iter = (0..).map(|i| match i {
+            0..=2 => ModInt::new(i + 5, m),
+            3 => ModInt::new(m, m),
+            _ => panic!("consumed past zero"),
+        });
+        assert!(product_with_zero_shortcut(iter).is_zero());
+        let iter = (0..).map(|i| match i {
+            0 => ModInt::new(6, 12),
+            1 => ModInt::new(2, 12),
+            _ => panic!("consumed past zero"),
+        });
+        assert!(product_with_zero_shortcut(iter).is_zero());
+    }
+
+    /// 等差数列の和 `first + (first + diff) + ... ` (`count`項)
+    ///
+    /// `count * first + diff * count(count - 1)/2`
+    ///
+    /// `count(count - 1)/2`は剰余をとる前に`u128`で割り切っておくので、
+    /// 2の逆元がない偶数のmodでも正しい
+    ///
+    /// # Panic
+    /// `first`と`diff`のmodが異なる時
+    pub fn arithmetic_sum(first: ModInt, diff: ModInt, count: u64) -> ModInt {
+        let (first, diff) = unify(first, diff);
+        let m = first.get_mod();
+        let c = count as u128;
+        let tri = (c * c.saturating_sub(1) / 2 % m as u128) as i64;
+        let count = (c % m as u128) as i64;
+        first * first.lift(count) + diff * first.lift(tri)
+    }
+
+    #[test]
+    fn arithmetic_sum_test() {
+        let naive = |first: ModInt, diff: ModInt, count: u64| {
+            let mut acc = ModInt::new(0, first.get_mod());
+            let mut x = first;
+            for _ in 0..count {
+                acc += x;
+                x += diff;
+            }
+            acc
+        };
+        for &m in &[1_000_000_007u32, 12, 2, 1 << 20] {
+            for &(a, d) in &[(3, 5), (0, 1), (-7, 11), (123_456, -654_321)] {
+                let (a, d) = (ModInt::new(a, m), ModInt::new(d, m));
+                for count in 0..20 {
+                    assert_eq!(arithmetic_sum(a, d, count), naive(a, d, count));
+                }
+            }
+        }
+        // 1 + 2 + ... + 10^18
+        let n = 1_000_000_000_000_000_000u64;
+        let m = 1_000_000_007;
+        let expected = (n as u128 * (n as u128 + 1) / 2 % m as u128) as i64;
+        let one = ModInt::new(1, m);
+        assert_eq!(arithmetic_sum(one, one, n).get(), expected);
+        // 偶数のmod
+        let m = 1 << 10;
+        let expected = (n as u128 * (n as u128 + 1) / 2 % m as u128) as i64;
+        let one = ModInt::new(1, m);
+        assert_eq!(arithmetic_sum(one, one, n).get(), expected);
+    }
+
+    /// modと互いに素な剰余(乗法群の元)を小さい順に列挙する
+    ///
+    /// `O(m log m)`
+    pub fn units(modulus: u32) -> impl Iterator<Item = ModInt> {
+        (0..modulus)
+            .filter(move |i| i.gcd(&modulus) == 1)
+            .map(move |i| ModInt::new(i, modulus))
+    }
+
+    #[test]
+    fn units_test() {
+        let u = units(12).map(|x| x.get()).collect::<Vec<_>>();
+        assert_eq!(u, vec![1, 5, 7, 11]);
+        assert_eq!(units(13).count(), 12);
+        // mod 1 では 0 だけ
+        assert_eq!(units(1).map(|x| x.get()).collect::<Vec<_>>(), vec![0]);
+        for x in units(30) {
+            assert!(x.checked_inv().is_some());
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    impl ModInt {
+        /// `[0, modulus)`から一様に選ぶ
+        pub fn random<R: rand::Rng>(rng: &mut R, modulus: u32) -> Self {
+            Self::new(rng.gen_range(0, modulus), modulus)
+        }
+    }
+
+    /// modと互いに素な(逆元を持つ)剰余を一様に選ぶ
+    ///
+    /// 互いに素なものが出るまで引き直す、合成数のmodでの`inv`や`div`のランダムテストに
+    #[cfg(feature = "rand")]
+    pub fn random_unit<R: rand::Rng>(rng: &mut R, modulus: u32) -> ModInt {
+        loop {
+            let x = ModInt::random(rng, modulus);
+            if x.get().gcd(&(modulus as i64)) == 1 {
+                return x;
+            }
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_unit_test() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(453);
+        for &m in &[12u32, 30, 1 << 20, 999_999_999, 1_000_000_007] {
+            for _ in 0..200 {
+                let x = random_unit(&mut rng, m);
+                assert_eq!(x.get_mod(), m as usize);
+                let inv = x.checked_inv().unwrap();
+                assert!((x * inv).is_one());
+            }
+        }
+        assert_eq!(random_unit(&mut rng, 1).get(), 0);
+    }
+
+    /// ヤコビ記号 `(a / n)`
+    ///
+    /// 平方剰余の相互法則で計算するので素因数分解は要らず、`O(log n)`
+    ///
+    /// # Panic
+    /// `n`が偶数の時
+    pub fn jacobi_symbol(a: i64, n: u64) -> i8 {
+        assert!(n % 2 == 1, "n must be odd");
+        // n > i64::MAX でも正しく剰余をとるため i128 で
+        let mut a = (a as i128).rem_euclid(n as i128) as u64;
+        let mut n = n;
+        let mut res = 1;
+        while a != 0 {
+            while a % 2 == 0 {
+                a /= 2;
+                // (2 / n) = -1  iff  n = 3, 5 (mod 8)
+                if n % 8 == 3 || n % 8 == 5 {
+                    res = -res;
+                }
+            }
+            std::mem::swap(&mut a, &mut n);
+            if a % 4 == 3 && n % 4 == 3 {
+                res = -res;
+            }
+            a %= n;
+        }
+        if n == 1 {
+            res
+        } else {
+            0
+        }
+    }
+
+    #[test]
+    fn legendre_jacobi_test() {
+        // mod 11 の平方剰余は 1, 3, 4, 5, 9
+        let qr = [1, 3, 4, 5, 9];
+        for a in 0..11 {
+            let expected = if a == 0 {
+                0
+            } else if qr.contains(&a) {
+                1
+            } else {
+                -1
+            };
+            assert_eq!(ModInt::new(a, 11).legendre_symbol(), expected);
+            assert_eq!(jacobi_symbol(a, 11), expected);
+        }
+        assert_eq!(ModInt::new(-1, 1_000_000_007).legendre_symbol(), -1);
+        assert_eq!(ModInt::new(-1, 998_244_353).legendre_symbol(), 1);
+
+        // 合成数の n はルジャンドル記号の積
+        assert_eq!(jacobi_symbol(2, 15), 1);
+        assert_eq!(jacobi_symbol(7, 15), -1);
+        assert_eq!(jacobi_symbol(5, 15), 0);
+        assert_eq!(jacobi_symbol(1001, 9907), -1);
+        assert_eq!(jacobi_symbol(19, 45), 1);
+        assert_eq!(jacobi_symbol(8, 21), -1);
+        assert_eq!(jacobi_symbol(-1, 7), -1);
+        assert_eq!(jacobi_symbol(0, 1), 1);
+        // 合成数modのルジャンドル記号は素数でないと分かればpanic
+        assert!(std::panic::catch_unwind(|| ModInt::new(2, 9).legendre_symbol()).is_err());
+
+        // i64 に収まらない n
+        assert_eq!(jacobi_symbol(-1, (1 << 63) + 1), 1);
+        assert_eq!(jacobi_symbol(-1, std::u64::MAX), -1);
+        assert_eq!(jacobi_symbol(2, std::u64::MAX), 1);
+        assert_eq!(jacobi_symbol(std::i64::MIN, (1 << 63) + 1), 1);
+        for n in (3..60u64).step_by(2) {
+            for a in -30..30 {
+                let by_factor = crate::prime::trial_division::factorization(n as usize)
+                    .into_iter()
+                    .map(|(p, e)| ModInt::new(a, p as u32).legendre_symbol().pow(e as u32))
+                    .product::<i8>();
+                assert_eq!(jacobi_symbol(a, n), by_factor);
+            }
+        }
+    }
+
+    /// 素数`p`について、乗法群の各元の位数ごとの個数
+    ///
+    /// 位数`d`の元は`phi(d)`個ある
+    ///
+    /// 全ての元の位数を求めるので`O(p sqrt p)`、数論の実験用
+    pub fn order_distribution(p: u32) -> HashMap<u64, u32> {
+        let mut res = HashMap::new();
+        for x in units(p) {
+            *res.entry(x.multiplicative_order().unwrap()).or_insert(0) += 1;
+        }
+        res
+    }
+
+    #[test]
+    fn order_distribution_test() {
+        let d = order_distribution(7);
+        assert_eq!(d.len(), 4);
+        assert_eq!(d[&1], 1);
+        assert_eq!(d[&2], 1);
+        assert_eq!(d[&3], 2);
+        assert_eq!(d[&6], 2);
+
+        // 位数dの元はphi(d)個
+        let d = order_distribution(13);
+        let phi = [(1, 1), (2, 1), (3, 2), (4, 2), (6, 2), (12, 4)];
+        assert_eq!(d.len(), phi.len());
+        for &(k, c) in &phi {
+            assert_eq!(d[&k], c);
+        }
+        assert_eq!(d.values().sum::<u32>(), 12);
+    }
+
+    /// 剰余`get()`の小さい順に並べ替える
+    ///
+    /// 異なるmodの比較を避けるため`Ord`は実装せず、全要素のmodが揃っていることを確かめてから並べる
+    ///
+    /// # Panic
+    /// 異なるmodの要素が混ざっていた時
+    pub fn sort_by_residue(v: &mut [ModInt]) {
+        if let Some(first) = v.first() {
+            for x in v.iter() {
+                if !check_mod_eq(first, x).1 {
+                    modulo_mismatch(first, x)
+                }
+            }
+        }
+        v.sort_by_key(|x| x.get());
+    }
+
+    #[test]
+    fn sort_by_residue_test() {
+        let m = 11;
+        let mut v = [5, -1, 3, 14, 0]
+            .iter()
+            .map(|&x| ModInt::new(x, m))
+            .collect::<Vec<_>>();
+        sort_by_residue(&mut v);
+        assert_eq!(
+            v.iter().map(|x| x.get()).collect::<Vec<_>>(),
+            vec![0, 3, 3, 5, 10]
+        );
+        sort_by_residue(&mut []);
+
+        let mut mixed = vec![ModInt::new(1, 7), ModInt::new(2, 11)];
+        assert!(std::panic::catch_unwind(move || sort_by_residue(&mut mixed)).is_err());
+    }
+
+    /// 累積和 `[a0, a0 + a1, a0 + a1 + a2, ...]`
+    ///
+    /// 先頭の要素のmodで始めるので、`Dynamic`な0から足し始めることはない
+    ///
+    /// # Panic
+    /// 異なるmodの要素が混ざっていた時
+    pub fn prefix_sums(a: &[ModInt]) -> Vec<ModInt> {
+        let mut res = Vec::with_capacity(a.len());
+        let mut iter = a.iter();
+        if let Some(&first) = iter.next() {
+            res.push(first);
+            let mut acc = first;
+            for &x in iter {
+                acc += x;
+                res.push(acc);
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn prefix_sums_test() {
+        let m = 7;
+        let a = vec![
+            ModInt::new(3, m),
+            ModInt::new(5, m),
+            ModInt::new(6, m),
+            ModInt::new(1, m),
+        ];
+        let s = prefix_sums(&a);
+        assert_eq!(
+            s.iter().map(|x| x.get()).collect::<Vec<_>>(),
+            vec![3, 1, 0, 1]
+        );
+        assert_eq!(s[3].get_mod(), 7);
+        assert!(prefix_sums(&[]).is_empty());
+    }
+
+    /// 累積積 `(prefix, suffix)`、`prefix[i] = a[0]...a[i - 1]`, `suffix[i] = a[i]...a[n - 1]`
+    ///
+    /// どちらも長さ`n + 1`で、端は先頭の要素のmodでの1 (`Dynamic`な1ではない)
+    ///
+    /// 自分以外の積は`prefix[i] * suffix[i + 1]`、逆元の一括計算などに
+    ///
+    /// 空なら`(vec![], vec![])`
+    ///
+    /// # Panic
+    /// 異なるmodの要素が混ざっていた時
+    pub fn prefix_suffix_products(a: &[ModInt]) -> (Vec<ModInt>, Vec<ModInt>) {
+        let first = match a.first() {
+            Some(&x) => x,
+            None => return (Vec::new(), Vec::new()),
+        };
+        for x in a {
+            if !check_mod_eq(&first, x).1 {
+                modulo_mismatch(&first, x)
+            }
+        }
+        let one = ModInt::new(1, first.get_mod());
+        let n = a.len();
+        let mut prefix = vec![one; n + 1];
+        let mut suffix = vec![one; n + 1];
+        for i in 0..n {
+            prefix[i + 1] = prefix[i] * a[i];
+            suffix[n - 1 - i] = suffix[n - i] * a[n - 1 - i];
+        }
+        (prefix, suffix)
+    }
+
+    #[test]
+    fn prefix_suffix_products_test() {
+        let m = 1_000_000_007;
+        let a = [3, 5, -2, 7, 11]
+            .iter()
+            .map(|&x| ModInt::new(x, m))
+            .collect::<Vec<_>>();
+        let (prefix, suffix) = prefix_suffix_products(&a);
+        assert_eq!(prefix.len(), 6);
+        assert_eq!(suffix.len(), 6);
+        for i in 0..=a.len() {
+            let p = a[..i].iter().fold(ModInt::new(1, m), |acc, &x| acc * x);
+            let s = a[i..].iter().fold(ModInt::new(1, m), |acc, &x| acc * x);
+            assert_eq!(prefix[i], p);
+            assert_eq!(suffix[i], s);
+            assert_eq!(prefix[i].get_mod(), m as usize);
+        }
+        // 自分以外の積
+        let total = prefix[a.len()];
+        for i in 0..a.len() {
+            assert_eq!(prefix[i] * suffix[i + 1] * a[i], total);
+        }
+        assert_eq!(prefix_suffix_products(&[]), (vec![], vec![]));
+        let mixed = [ModInt::new(1, 7), ModInt::new(1, 11)];
+        assert!(std::panic::catch_unwind(|| prefix_suffix_products(&mixed)).is_err());
+    }
+
+    /// 内積 `a[0]b[0] + a[1]b[1] + ...`
+    ///
+    /// 積を`u128`に溜めて最後に一度だけ剰余をとる
+    ///
+    /// 空なら`Dynamic`な0、modはどちらかの列の最初の`Static`な要素から決める
+    ///
+    /// # Panic
+    /// 長さが異なる時、異なるmodの要素が混ざっていた時、空でなく全要素が`Dynamic`な時
+    pub fn dot(a: &[ModInt], b: &[ModInt]) -> ModInt {
+        assert_eq!(a.len(), b.len(), "length mismatch");
+        if a.is_empty() {
+            return ModInt::zero();
+        }
+        let anchor = *a
+            .iter()
+            .chain(b.iter())
+            .find(|x| x._modulo.get().is_some())
+            .expect("modulus unknown: every element is dynamic");
+        let mut acc = 0u128;
+        for (&x, &y) in a.iter().zip(b.iter()) {
+            let (_, x) = unify(anchor, x);
+            let (_, y) = unify(anchor, y);
+            acc += x.num as u128 * y.num as u128;
+        }
+        ModInt {
+            num: narrow(acc % anchor.get_mod() as u128),
+            _modulo: anchor._modulo,
+        }
+    }
+
+    #[test]
+    fn dot_test() {
+        let m = 998_244_353;
+        let a = (0..100)
+            .map(|i| ModInt::new(i * 12_345_677 + 3, m))
+            .collect::<Vec<_>>();
+        let b = (0..100)
+            .map(|i| ModInt::new(m as i64 - 1 - i * 7, m))
+            .collect::<Vec<_>>();
+        let naive = a
+            .iter()
+            .zip(b.iter())
+            .map(|(&x, &y)| x * y)
+            .fold(ModInt::new(0, m), |acc, e| acc + e);
+        assert_eq!(dot(&a, &b), naive);
+        assert_eq!(dot(&a[..1], &b[..1]), a[0] * b[0]);
+        assert!(dot(&[], &[]).is_zero());
+
+        // 先頭がDynamicでも後ろのStaticな要素からmodを決める
+        let mut c = a.clone();
+        c[0] = ModInt::zero();
+        c[1] = "9000000000000000000".parse().unwrap();
+        let expected = a[2..]
+            .iter()
+            .zip(b[2..].iter())
+            .map(|(&x, &y)| x * y)
+            .fold(ModInt::new(0, m), |acc, e| acc + e)
+            + b[1] * ModInt::new(narrow(9_000_000_000_000_000_000u128 % m as u128), m);
+        assert_eq!(dot(&c, &b), expected);
+        assert_eq!(dot(&[ModInt::one()], &[ModInt::new(5, 7)]).get_mod(), 7);
+        assert!(std::panic::catch_unwind(|| dot(&[ModInt::zero()], &[ModInt::one()])).is_err());
+    }
+
+    /// `dst[i] += scalar * src[i]`
+    ///
+    /// ガウスの消去法で行を足し込むときの核、積は`u128`で計算する
+    ///
+    /// # Panic
+    /// 長さが異なる時、異なるmodの要素が混ざっていた時
+    pub fn axpy(dst: &mut [ModInt], scalar: ModInt, src: &[ModInt]) {
+        assert_eq!(dst.len(), src.len(), "length mismatch");
+        for (d, &x) in dst.iter_mut().zip(src.iter()) {
+            let (s, x) = unify(scalar, x);
+            *d += ModInt {
+                num: mul_mod(s.num, x.num, s.get_mod()),
+                _modulo: s._modulo,
+            };
+        }
+    }
+
+    #[test]
+    fn axpy_test() {
+        let m = 4_294_967_291u32;
+        let src = (0..50)
+            .map(|i| ModInt::new(m as i64 - 1 - i * 77_777_777, m))
+            .collect::<Vec<_>>();
+        let mut dst = (0..50)
+            .map(|i| ModInt::new(i as i64 * 123_456_789, m))
+            .collect::<Vec<_>>();
+        let scalar = ModInt::new(m - 2, m);
+        let naive = dst
+            .iter()
+            .zip(src.iter())
+            .map(|(&d, &x)| d + scalar * x)
+            .collect::<Vec<_>>();
+        axpy(&mut dst, scalar, &src);
+        assert_eq!(dst, naive);
+
+        axpy(&mut [], scalar, &[]);
+        let mut short = vec![ModInt::new(0, m)];
+        assert!(std::panic::catch_unwind(move || axpy(&mut short, scalar, &[])).is_err());
+        let mut mixed = vec![ModInt::new(0, 7)];
+        assert!(std::panic::catch_unwind(move || axpy(
+            &mut mixed,
+            ModInt::new(1, 7),
+            &[ModInt::new(1, 11)]
+        ))
+        .is_err());
+    }
+
+    /// 剰余をとらずに`u128`へ足し込み、最後に一度だけ剰余をとる
+    ///
+    /// 溢れそうになった時だけ途中で剰余をとるので、長い列の和や内積で`%`の回数を減らせる
+    #[derive(Debug, Clone)]
+    pub struct ModAccumulator {
+        total: u128,
+        modulus: u32,
+    }
+
+    impl ModAccumulator {
+        pub fn new(modulus: u32) -> Self {
+            assert!(modulus != 0, "modulo number may be wrong");
+            Self { total: 0, modulus }
+        }
+
+        fn push(&mut self, v: u128) {
+            // v < 2^64 なので、これ以下なら足しても溢れない
+            const LIMIT: u128 = std::u128::MAX - (1 << 64);
+            if self.total > LIMIT {
+                self.total %= self.modulus as u128;
+            }
+            self.total += v;
+        }
+
+        /// modを確かめ、`self.modulus`で剰余をとった値
+        ///
+        /// Dynamicな値は`get()`が大きくなりうるので、ここで`v < 2^64`に収める
+        fn reduce(&self, x: &ModInt) -> u128 {
+            if x._modulo.get().map_or(false, |m| m != self.modulus) {
+                modulo_mismatch(&ModInt::new(0, self.modulus), x)
+            }
+            compensated_rem(x.get(), self.modulus as usize) as u128
+        }
+
+        /// `total += x`
+        ///
+        /// # Panic
+        /// `x`のmodが異なる時
+        pub fn add(&mut self, x: ModInt) {
+            let v = self.reduce(&x);
+            self.push(v);
+        }
+
+        /// `total += x * y`
+        ///
+        /// # Panic
+        /// `x`, `y`のmodが異なる時
+        pub fn add_mul(&mut self, x: ModInt, y: ModInt) {
+            let v = self.reduce(&x) * self.reduce(&y);
+            self.push(v);
+        }
+
+        /// ここまでの和
+        pub fn finish(&self) -> ModInt {
+            ModInt::new(narrow(self.total % self.modulus as u128), self.modulus)
+        }
+    }
+
+    #[test]
+    fn mod_accumulator_test() {
+        let m = 4_294_967_291u32;
+        let v = (0..100_000)
+            .map(|i| ModInt::new(m as i64 - 1 - i * 40_503, m))
+            .collect::<Vec<_>>();
+        let mut acc = ModAccumulator::new(m);
+        let mut naive = ModInt::new(0, m);
+        for &x in &v {
+            acc.add(x);
+            naive += x;
+        }
+        assert_eq!(acc.finish(), naive);
+
+        // 内積
+        let mut acc = ModAccumulator::new(m);
+        for (&x, &y) in v.iter().zip(v.iter().rev()) {
+            acc.add_mul(x, y);
+        }
+        assert_eq!(
+            acc.finish(),
+            dot(&v, &v.iter().rev().cloned().collect::<Vec<_>>())
+        );
+
+        // 途中での剰余
+        let mut acc = ModAccumulator::new(m);
+        acc.total = std::u128::MAX - 5;
+        acc.add(ModInt::new(m - 1, m));
+        let expected = ((std::u128::MAX - 5) % m as u128 + m as u128 - 1) % m as u128;
+        assert_eq!(acc.finish().get(), expected as i64);
+
+        // Dynamicな大きい値も剰余をとってから足し込む
+        let big: ModInt = "9000000000000000000".parse().unwrap();
+        let mut acc = ModAccumulator::new(m);
+        for _ in 0..10 {
+            acc.add_mul(big, big);
+            acc.add(big);
+        }
+        let b = 9_000_000_000_000_000_000u128 % m as u128;
+        let expected = (b * b % m as u128 + b) * 10 % m as u128;
+        assert_eq!(acc.finish().get(), expected as i64);
+
+        assert!(ModAccumulator::new(7).finish().is_zero());
+        let bad = std::panic::catch_unwind(|| ModAccumulator::new(7).add(ModInt::new(1, 11)));
+        assert_eq!(
+            bad.unwrap_err().downcast_ref::<String>().unwrap(),
+            "modulus mismatch: lhs mod 7 vs rhs mod 11"
+        );
+    }
+
+    impl Sum for ModInt {
+        fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+            fold_mod(iter, |a, b| a + b).unwrap_or_else(Self::zero)
+        }
+    }
+
+    impl Product for ModInt {
+        fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+            fold_mod(iter, |a, b| a * b).unwrap_or_else(Self::one)
+        }
+    }
+
+    #[test]
+    fn fold_test() {
+        let v = vec![ModInt::new(3, 7), ModInt::new(5, 7), ModInt::new(6, 7)];
+        assert_eq!(fold_mod(v.clone(), |a, b| a + b).unwrap().get(), 0);
+        assert_eq!(v.iter().cloned().sum::<ModInt>().get(), 0);
+        assert_eq!(v.iter().cloned().product::<ModInt>().get(), 6);
+
+        // 剰余の最小値
+        let min = fold_mod(v, |a, b| if a.get() <= b.get() { a } else { b });
+        assert_eq!(min.unwrap().get(), 3);
+
+        assert!(fold_mod(vec![], |a, b| a + b).is_none());
+    }
+
+    /// `1..=up_to`の逆元を前計算しておき、小さい定数での割り算を`O(1)`で行う
+    ///
+    /// 初期化 `O(up_to)`、modは`up_to`より大きい素数であること
+    #[derive(Debug, Clone)]
+    pub struct SmallInvCache {
+        modulus: u32,
+        inv: Vec<ModInt>,
+    }
+
+    impl SmallInvCache {
+        pub fn new(modulus: u32, up_to: usize) -> Self {
+            assert!(up_to < modulus as usize, "up_to must be less than modulus");
+            let m = modulus as usize;
+            let mut inv = vec![ModInt::new(0, modulus); up_to + 1];
+            if up_to >= 1 {
+                inv[1] = ModInt::new(1, modulus);
+            }
+            // inv[i] = -(m / i) * inv[m % i]
+            for i in 2..=up_to {
+                inv[i] = inv[m % i] * (m - m / i);
+            }
+            Self { modulus, inv }
+        }
+
+        /// `d^(-1)`
+        ///
+        /// # Panic
+        /// `d == 0`または`d > up_to`の時
+        pub fn inv(&self, d: usize) -> ModInt {
+            assert!(d != 0, "division by zero");
+            self.inv[d]
+        }
+
+        /// `x / d`
+        ///
+        /// # Panic
+        /// `d == 0`または`d > up_to`の時、`x`のmodが異なる時
+        pub fn div_small(&self, x: ModInt, d: usize) -> ModInt {
+            x * self.inv(d)
+        }
+
+        pub fn modulus(&self) -> u32 {
+            self.modulus
+        }
+    }
+
+    #[test]
+    fn small_inv_cache_test() {
+        let m = 1_000_000_007;
+        let cache = SmallInvCache::new(m, 10);
+        let x = ModInt::new(123_456_789, m);
+        for &d in &[2, 6] {
+            let expected = x * ModInt::new(d, m).checked_inv().unwrap();
+            assert_eq!(cache.div_small(x, d), expected);
+            assert_eq!(cache.div_small(x, d) * d, x);
+        }
+        for d in 1..=10 {
+            assert_eq!(cache.inv(d) * d, ModInt::new(1, m));
+        }
+    }
+
+    /// 調和数 `1/1 + 1/2 + ... + 1/n mod p`
+    ///
+    /// 逆元の表を作るので`O(n)`、modは`n`より大きい素数であること
+    pub fn harmonic(n: usize, modulus: u32) -> ModInt {
+        let cache = SmallInvCache::new(modulus, n);
+        (1..=n).fold(ModInt::new(0, modulus), |acc, i| acc + cache.inv(i))
+    }
+
+    #[test]
+    fn harmonic_test() {
+        let p = 1_000_000_007;
+        assert_eq!(harmonic(0, p).get(), 0);
+        assert_eq!(harmonic(1, p).get(), 1);
+        for n in 1..30 {
+            let direct = (1..=n).fold(ModInt::new(0, p), |acc, i| {
+                acc + ModInt::new(i, p).checked_inv().unwrap()
+            });
+            assert_eq!(harmonic(n, p), direct);
+        }
+        // H_3 = 11/6
+        assert_eq!(harmonic(3, p) * 6, ModInt::new(11, p));
+    }
+
+    /// 素数`p`を法とする平方根をTonelli–Shanks法で求める
+    ///
+    /// `p - 1 = Q 2^S`の分解と平方非剰余を一度だけ求めておき、同じ`p`での多数の問い合わせに使い回す
+    ///
+    /// 初期化 期待`O(log p)`、1回の問い合わせ `O(log^2 p)`
+    #[derive(Debug, Clone)]
+    pub struct SqrtSolver {
+        p: u32,
+        q: usize,
+        s: u32,
+        non_residue: ModInt,
+    }
+
+    impl SqrtSolver {
+        /// `p`は素数であること (検査しない)
+        ///
+        /// # Panic
+        /// 平方非剰余が見つからない(`p`が素数でない)時
+        pub fn new(p: u32) -> Self {
+            assert!(p >= 2, "p must be prime");
+            let mut q = p as usize - 1;
+            let mut s = 0;
+            while q > 0 && q % 2 == 0 {
+                q /= 2;
+                s += 1;
+            }
+            let non_residue = if p == 2 {
+                ModInt::new(1, p)
+            } else {
+                (1..p)
+                    .map(|z| ModInt::new(z, p))
+                    .find(|z| z.legendre_symbol() == -1)
+                    .expect("p must be prime")
+            };
+            Self {
+                p,
+                q,
+                s,
+                non_residue,
+            }
+        }
+
+        pub fn modulus(&self) -> u32 {
+            self.p
+        }
+
+        /// `x^2 = a`となる`x`のうち小さい方、平方非剰余なら`None`
+        ///
+        /// # Panic
+        /// `a`のmodが`p`でない時
+        pub fn solve(&self, a: ModInt) -> Option<ModInt> {
+            let a = ModInt::new(0, self.p) + a;
+            if self.p == 2 || a.get() == 0 {
+                return Some(a);
+            }
+            if a.legendre_symbol() != 1 {
+                return None;
+            }
+            let mut level = self.s;
+            let mut factor = self.non_residue.pow(self.q);
+            let mut t = a.pow(self.q);
+            let mut res = a.pow((self.q + 1) / 2);
+            while t.get() != 1 {
+                // t^(2^i) = 1 となる最小の i
+                let mut i = 0;
+                let mut t2 = t;
+                while t2.get() != 1 {
+                    t2 = t2.square();
+                    i += 1;
+                }
+                let mut b = factor;
+                for _ in 0..level - i - 1 {
+                    b = b.square();
+                }
+                level = i;
+                factor = b.square();
+                t *= factor;
+                res *= b;
+            }
+            let other = ModInt::new(0, self.p) - res;
+            Some(if other.get() < res.get() { other } else { res })
+        }
+    }
+
+    impl ModInt {
+        /// 素数modでの平方根のうち小さい方、平方非剰余なら`None`
+        ///
+        /// `p - 1 = Q 2^S`の`S`が大きい(`S^2 > log p`)時はCipolla法、そうでなければTonelli–Shanks法
+        ///
+        /// 毎回`SqrtSolver`を作るので、同じmodで何度も求めるなら`SqrtSolver`を使う
+        ///
+        /// # Panic
+        /// modが素数でないと分かった時 (平方非剰余が見つからない時)
+        pub fn sqrt(&self) -> Option<ModInt> {
+            let p = self.get_mod() as u32;
+            let s = (p.max(2) - 1).trailing_zeros();
+            if p > 2 && s * s > 32 - p.leading_zeros() {
+                self.sqrt_cipolla()
+            } else {
+                SqrtSolver::new(p).solve(*self)
+            }
+        }
+
+        /// Cipolla法による素数modでの平方根のうち小さい方、平方非剰余なら`None`
+        ///
+        /// `a^2 - self`が平方非剰余となる`a`をとり、`F_p(sqrt(a^2 - self))`で
+        /// `(a + sqrt(a^2 - self))^((p + 1) / 2)`を計算する
+        ///
+        /// 期待`O(log p)`、`p - 1`の2冪の因子によらない
+        ///
+        /// # Panic
+        /// modが素数でないと分かった時 (`a^2 - self`が平方非剰余となる`a`がない時)
+        pub fn sqrt_cipolla(&self) -> Option<ModInt> {
+            let p = self.get_mod();
+            if p == 2 || self.get() == 0 {
+                return Some(*self);
+            }
+            if self.legendre_symbol() != 1 {
+                return None;
+            }
+            let (base, w) = (0..p)
+                .map(|a| self.lift(a as i64))
+                .map(|a| (a, a.square() - *self))
+                .find(|(_, w)| w.legendre_symbol() == -1)
+                .expect("modulus must be prime");
+            // (x, y) = x + y sqrt(w)
+            let mul = |(x1, y1): (ModInt, ModInt), (x2, y2): (ModInt, ModInt)| {
+                (x1 * x2 + y1 * y2 * w, x1 * y2 + x2 * y1)
+            };
+            let mut acc = (self.lift(1), self.lift(0));
+            let mut pow = (base, self.lift(1));
+            let mut exp = (p + 1) / 2;
+            while exp > 0 {
+                if exp & 1 == 1 {
+                    acc = mul(acc, pow);
+                }
+                pow = mul(pow, pow);
+                exp >>= 1;
+            }
+            let root = acc.0;
+            let other = -root;
+            Some(if other.get() < root.get() {
+                other
+            } else {
+                root
+            })
+        }
+    }
+
+    #[test]
+    fn sqrt_solver_test() {
+        for &p in &[2u32, 3, 5, 13, 17, 998_244_353, 1_000_000_007] {
+            let solver = SqrtSolver::new(p);
+            assert_eq!(solver.modulus(), p);
+            for a in (0..200).map(|i| ModInt::new(i * 7_654_321 + i, p)) {
+                let r = solver.solve(a);
+                assert_eq!(r, a.sqrt());
+                match r {
+                    Some(x) => {
+                        assert_eq!(x.square(), a);
+                        assert!(x.get() <= (p / 2) as i64);
+                    }
+                    None => assert_eq!(a.is_quadratic_residue(), Some(false)),
+                }
+            }
+        }
+        // 2冪の因子が大きいmodも小さいmodも
+        for &p in &[3u32, 5, 13, 17, 97, 257, 65_537, 998_244_353, 1_000_000_007] {
+            let solver = SqrtSolver::new(p);
+            for a in (0..300).map(|i| ModInt::new(i * 1_234_567 + 1, p)) {
+                assert_eq!(a.sqrt_cipolla(), solver.solve(a));
+            }
+        }
+
+        // 1e9 + 7 の平方根の例
+        let solver = SqrtSolver::new(1_000_000_007);
+        assert_eq!(
+            solver.solve(ModInt::new(4, 1_000_000_007)).unwrap().get(),
+            2
+        );
+        assert_eq!(solver.solve(ModInt::new(5, 1_000_000_007)), None);
+
+        // 合成数modではメッセージ付きでpanic
+        assert!(std::panic::catch_unwind(|| SqrtSolver::new(9)).is_err());
+        assert!(std::panic::catch_unwind(|| SqrtSolver::new(15)).is_err());
+        assert!(std::panic::catch_unwind(|| ModInt::new(1, 9).sqrt()).is_err());
+        assert!(std::panic::catch_unwind(|| ModInt::new(1, 15).sqrt()).is_err());
+    }
+
+    thread_local! {
+        static DEFAULT_MODULUS: std::cell::Cell<Option<u32>> = std::cell::Cell::new(None);
+    }
+
+    /// `mint!`で使うmodを設定する
+    ///
+    /// スレッドごとの大域状態なので、別のスレッドからは見えない。
+    /// 途中で変えると、それまでに作った値とは異なるmodになるので注意
+    pub fn set_default_modulus(m: u32) {
+        assert!(m != 0, "modulo number may be wrong");
+        DEFAULT_MODULUS.with(|d| d.set(Some(m)));
+    }
+
+    /// `set_default_modulus`で設定したmod
+    ///
+    /// # Panic
+    /// まだ設定されていない時
+    pub fn default_modulus() -> u32 {
+        DEFAULT_MODULUS
+            .with(|d| d.get())
+            .expect("default modulus is not set")
+    }
+
+    #[test]
+    fn default_modulus_test() {
+        // --test-threads=1 では他のテストとスレッドを共有しうるので、未設定の新しいスレッドで
+        std::thread::spawn(|| {
+            assert_eq!(DEFAULT_MODULUS.with(|d| d.get()), None);
+            set_default_modulus(13);
+            assert_eq!(default_modulus(), 13);
+            let a = crate::mint!(20);
+            assert_eq!(a, ModInt::new(7, 13));
+            assert_eq!(crate::mint!(-1).get(), 12);
+            assert_eq!(crate::mint!(5) * crate::mint!(8), ModInt::new(1, 13));
+
+            set_default_modulus(1_000_000_007);
+            assert_eq!(crate::mint!(1_000_000_008).get(), 1);
+            // 別のスレッドで設定しても、このスレッドのmodは変わらない
+            std::thread::spawn(|| set_default_modulus(7))
+                .join()
+                .unwrap();
+            assert_eq!(default_modulus(), 1_000_000_007);
+        })
+        .join()
+        .unwrap();
+    }
 }
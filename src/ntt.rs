@@ -0,0 +1,343 @@
+use crate::modint::{moduli, ModInt};
+use crate::polynomial::{karatsuba, KARATSUBA_THRESHOLD};
+use num_traits::Pow;
+use std::collections::HashMap;
+
+/// 素数`modulus`の原始根
+///
+/// よく使うmodは表から、それ以外は小さい方から`is_primitive_root`で探す
+pub fn primitive_root(modulus: u32) -> ModInt {
+    if let Some(g) = moduli::known_primitive_root(modulus) {
+        return ModInt::new(g, modulus);
+    }
+    (1..modulus)
+        .map(|g| ModInt::new(g, modulus))
+        .find(|g| g.is_primitive_root().expect("modulus must be prime"))
+        .unwrap()
+}
+
+/// 原始`2^log_n`乗根`w`の冪 `[1, w, w^2, ..., w^(2^log_n - 1)]`
+///
+/// # Panic
+/// `modulus - 1`が`2^log_n`で割り切れない時
+pub fn roots_of_unity(modulus: u32, log_n: u32) -> Vec<ModInt> {
+    RootTable::new(modulus, log_n).forward
+}
+
+/// NTTのバタフライで使う1の冪根の表
+///
+/// `forward[i] = w^i`, `inverse[i] = w^(-i)`
+#[derive(Debug, Clone)]
+pub struct RootTable {
+    pub log_n: u32,
+    pub forward: Vec<ModInt>,
+    pub inverse: Vec<ModInt>,
+}
+
+impl RootTable {
+    /// # Panic
+    /// `modulus - 1`が`2^log_n`で割り切れない時
+    pub fn new(modulus: u32, log_n: u32) -> Self {
+        assert!(log_n < 32, "log_n is too large");
+        let n = 1u64 << log_n;
+        assert!(
+            (modulus as u64 - 1) % n == 0,
+            "modulus - 1 = {} is not divisible by 2^{}",
+            modulus - 1,
+            log_n
+        );
+        let w = primitive_root(modulus).pow(((modulus as u64 - 1) / n) as usize);
+        let iw = w.checked_inv().unwrap();
+        let forward = w.powers().take(n as usize).collect();
+        let inverse = iw.powers().take(n as usize).collect();
+        Self {
+            log_n,
+            forward,
+            inverse,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.forward.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.forward.is_empty()
+    }
+}
+
+/// 数論変換 (in-place, 反復版)
+///
+/// `invert`なら逆変換し、`1/n`倍まで行う
+///
+/// `O(n log n)`
+///
+/// # Panic
+/// `a.len()`が`table.len()`と異なる時
+pub fn ntt(a: &mut [ModInt], table: &RootTable, invert: bool) {
+    let n = a.len();
+    assert_eq!(n, table.len(), "length must match the root table");
+    if n <= 1 {
+        return;
+    }
+    // ビット反転の並べ替え
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+    let roots = if invert {
+        &table.inverse
+    } else {
+        &table.forward
+    };
+    let mut len = 2;
+    while len <= n {
+        let step = n / len;
+        for block in a.chunks_mut(len) {
+            let (lo, hi) = block.split_at_mut(len / 2);
+            for (k, (x, y)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                let t = y.mul_root_power(roots, k * step);
+                *y = *x - t;
+                *x += t;
+            }
+        }
+        len <<= 1;
+    }
+    if invert {
+        let inv_n = ModInt::new(n, a[0].get_mod()).checked_inv().unwrap();
+        for x in a.iter_mut() {
+            *x *= inv_n;
+        }
+    }
+}
+
+/// modと長さごとに`RootTable`を覚えておき、同じ大きさの変換を繰り返すときに作り直さない
+///
+/// 多項式の冪などNTTを何度も呼ぶ処理で使う
+#[derive(Debug, Clone, Default)]
+pub struct NttPlanner {
+    tables: HashMap<(u32, u32), RootTable>,
+}
+
+impl NttPlanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `(modulus, log_n)`の表、なければ作る
+    pub fn table(&mut self, modulus: u32, log_n: u32) -> &RootTable {
+        self.tables
+            .entry((modulus, log_n))
+            .or_insert_with(|| RootTable::new(modulus, log_n))
+    }
+
+    /// 覚えている表の数
+    pub fn cached(&self) -> usize {
+        self.tables.len()
+    }
+
+    /// `a`を数論変換する、`inverse`なら逆変換 (`1/n`倍まで)
+    ///
+    /// modは`a[0]`のもの
+    ///
+    /// # Panic
+    /// `a.len()`が2冪でない時、`modulus - 1`が`a.len()`で割り切れない時
+    pub fn transform(&mut self, a: &mut [ModInt], inverse: bool) {
+        if a.len() <= 1 {
+            return;
+        }
+        assert!(a.len().is_power_of_two(), "length must be a power of two");
+        let log_n = a.len().trailing_zeros();
+        let table = self.table(a[0].get_mod() as u32, log_n);
+        ntt(a, table, inverse);
+    }
+}
+
+/// 畳み込み `c[k] = sum_{i + j = k} a[i] b[j]`
+///
+/// `m - 1`が結果の長さ以上の2冪で割り切れるmod(998244353など)ならNTTで`O(n log n)`、
+/// そうでなければKaratsuba法で`O(n^1.59)`
+///
+/// # Panic
+/// `a`と`b`のmodが異なる時
+pub fn convolution(a: &[ModInt], b: &[ModInt]) -> Vec<ModInt> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let modulus = a[0].get_mod() as u32;
+    let len = a.len() + b.len() - 1;
+    let mut log_n = 0;
+    while (1usize << log_n) < len {
+        log_n += 1;
+    }
+    let n = 1usize << log_n;
+    if (modulus as u64 - 1) % n as u64 != 0 || len <= KARATSUBA_THRESHOLD {
+        return karatsuba(a, b);
+    }
+    let table = RootTable::new(modulus, log_n);
+    let zero = ModInt::new(0, modulus);
+    let mut fa = a.to_vec();
+    fa.resize(n, zero);
+    let mut fb = b.to_vec();
+    fb.resize(n, zero);
+    ntt(&mut fa, &table, false);
+    ntt(&mut fb, &table, false);
+    for (x, &y) in fa.iter_mut().zip(fb.iter()) {
+        *x *= y;
+    }
+    ntt(&mut fa, &table, true);
+    fa.truncate(len);
+    fa
+}
+
+/// `a`から1つ、`b`から1つ選んだ和が`s`になる組の数を`res[s]`に
+///
+/// 値ごとの出現回数を係数とする多項式の積
+///
+/// 結果の長さは`max(a) + max(b) + 1`
+pub fn count_convolution(a: &[usize], b: &[usize], modulus: u32) -> Vec<ModInt> {
+    let freq = |v: &[usize]| {
+        let max = match v.iter().max() {
+            Some(&m) => m,
+            None => return Vec::new(),
+        };
+        let mut f = vec![ModInt::new(0, modulus); max + 1];
+        for &x in v {
+            f[x] += 1;
+        }
+        f
+    };
+    convolution(&freq(a), &freq(b))
+}
+
+#[test]
+fn primitive_root_test() {
+    assert_eq!(primitive_root(998_244_353).get(), 3);
+    assert_eq!(primitive_root(7).get(), 3);
+    assert_eq!(primitive_root(13).get(), 2);
+    assert_eq!(primitive_root(2).get(), 1);
+}
+
+#[test]
+fn roots_of_unity_test() {
+    let m = 998_244_353;
+    for log_n in 0..=10 {
+        let n = 1usize << log_n;
+        let roots = roots_of_unity(m, log_n);
+        assert_eq!(roots.len(), n);
+        for &r in &roots {
+            assert_eq!(r.pow(n).get(), 1);
+        }
+        // 原始根なので全て異なる
+        let mut v = roots.iter().map(|r| r.get()).collect::<Vec<_>>();
+        v.sort();
+        v.dedup();
+        assert_eq!(v.len(), n);
+    }
+
+    let table = RootTable::new(m, 5);
+    for (&f, &i) in table.forward.iter().zip(table.inverse.iter()) {
+        assert_eq!((f * i).get(), 1);
+    }
+
+    // 1e9 + 7 - 1 = 2 * 500000003
+    assert_eq!(roots_of_unity(1_000_000_007, 1).len(), 2);
+    assert!(std::panic::catch_unwind(|| roots_of_unity(1_000_000_007, 2)).is_err());
+}
+
+#[test]
+fn ntt_roundtrip_test() {
+    let m = 998_244_353;
+    let table = RootTable::new(m, 4);
+    let a = (0..16)
+        .map(|i| ModInt::new(i * i + 3, m))
+        .collect::<Vec<_>>();
+    let mut b = a.clone();
+    ntt(&mut b, &table, false);
+    assert_ne!(a, b);
+    ntt(&mut b, &table, true);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn ntt_planner_test() {
+    let mut planner = NttPlanner::new();
+    for &m in &[998_244_353, 469_762_049] {
+        for round in 0..20 {
+            let log_n = round % 6;
+            let a = (0..1i64 << log_n)
+                .map(|i| ModInt::new(i * 7919 + round as i64, m))
+                .collect::<Vec<_>>();
+            let mut fresh = a.clone();
+            ntt(&mut fresh, &RootTable::new(m, log_n), false);
+            let mut planned = a.clone();
+            planner.transform(&mut planned, false);
+            assert_eq!(planned, fresh);
+            planner.transform(&mut planned, true);
+            assert_eq!(planned, a);
+        }
+    }
+    // 長さ1は表を作らない
+    assert_eq!(planner.cached(), 2 * 5);
+    let mut a = vec![ModInt::new(1, 998_244_353); 3];
+    assert!(std::panic::catch_unwind(move || NttPlanner::new().transform(&mut a, false)).is_err());
+}
+
+#[test]
+fn convolution_test() {
+    let naive = |a: &[ModInt], b: &[ModInt]| {
+        let mut res = vec![ModInt::new(0, a[0].get_mod()); a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                res[i + j] += x * y;
+            }
+        }
+        res
+    };
+    for &m in &[998_244_353, 1_000_000_007] {
+        let a = (0..100)
+            .map(|i| ModInt::new(i * 31 + 7, m))
+            .collect::<Vec<_>>();
+        let b = (0..77)
+            .map(|i| ModInt::new(m as i64 - i * 13, m))
+            .collect::<Vec<_>>();
+        assert_eq!(convolution(&a, &b), naive(&a, &b));
+    }
+    assert!(convolution(&[], &[ModInt::new(1, 7)]).is_empty());
+}
+
+#[test]
+fn count_convolution_test() {
+    let m = 998_244_353;
+    let a = [1, 2, 2, 5];
+    let b = [0, 3, 3];
+    let c = count_convolution(&a, &b, m);
+    let mut expected = vec![0; 5 + 3 + 1];
+    for &x in &a {
+        for &y in &b {
+            expected[x + y] += 1;
+        }
+    }
+    assert_eq!(c.iter().map(|x| x.get()).collect::<Vec<_>>(), expected);
+
+    // NTTを使う大きさ
+    let a = (0..200).map(|i| i % 37).collect::<Vec<_>>();
+    let b = (0..300).map(|i| (i * 7) % 50).collect::<Vec<_>>();
+    let c = count_convolution(&a, &b, m);
+    let mut expected = vec![0; 36 + 49 + 1];
+    for &x in &a {
+        for &y in &b {
+            expected[x + y] += 1;
+        }
+    }
+    assert_eq!(c.iter().map(|x| x.get()).collect::<Vec<_>>(), expected);
+    assert!(count_convolution(&[], &b, m).is_empty());
+}
@@ -0,0 +1,318 @@
+use crate::modint::ModInt;
+use crate::ntt::convolution;
+use std::ops::{Add, Index, Mul, Sub};
+
+/// `mod m`上の多項式
+///
+/// `coef[i]`が`x^i`の係数、末尾の0は持たない
+#[derive(Debug, Clone, PartialEq)]
+pub struct Poly {
+    modulus: u32,
+    coef: Vec<ModInt>,
+}
+
+impl Poly {
+    pub fn new(mut coef: Vec<ModInt>, modulus: u32) -> Self {
+        while coef.last().map_or(false, |c| c.get() == 0) {
+            coef.pop();
+        }
+        Self { modulus, coef }
+    }
+
+    /// 整数の係数列から
+    pub fn from_slice(coef: &[i64], modulus: u32) -> Self {
+        Self::new(
+            coef.iter().map(|&c| ModInt::new(c, modulus)).collect(),
+            modulus,
+        )
+    }
+
+    pub fn zero(modulus: u32) -> Self {
+        Self::new(Vec::new(), modulus)
+    }
+
+    pub fn one(modulus: u32) -> Self {
+        Self::new(vec![ModInt::new(1, modulus)], modulus)
+    }
+
+    pub fn modulus(&self) -> u32 {
+        self.modulus
+    }
+
+    /// 係数の個数(次数 + 1)、零多項式なら0
+    pub fn len(&self) -> usize {
+        self.coef.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.coef.is_empty()
+    }
+
+    /// 次数、零多項式なら`None`
+    pub fn degree(&self) -> Option<usize> {
+        self.len().checked_sub(1)
+    }
+
+    pub fn coef(&self) -> &[ModInt] {
+        &self.coef
+    }
+
+    /// `x^n`未満の項だけを残す
+    pub fn truncate(&self, n: usize) -> Self {
+        Self::new(self.coef.iter().take(n).cloned().collect(), self.modulus)
+    }
+
+    /// `x`での値をHornerで
+    pub fn eval(&self, x: ModInt) -> ModInt {
+        self.coef
+            .iter()
+            .rev()
+            .fold(ModInt::new(0, self.modulus), |acc, &c| acc * x + c)
+    }
+
+    /// `self * rhs mod x^n`
+    ///
+    /// `O(n^2)`
+    pub fn mul_trunc(&self, rhs: &Self, n: usize) -> Self {
+        let len = (self.len() + rhs.len()).saturating_sub(1).min(n);
+        let mut res = vec![ModInt::new(0, self.modulus); len];
+        for (i, &a) in self.coef.iter().enumerate().take(len) {
+            for (j, &b) in rhs.coef.iter().enumerate().take(len - i) {
+                res[i + j] += a * b;
+            }
+        }
+        Self::new(res, self.modulus)
+    }
+}
+
+/// # Panic
+/// 次数を超える時、0を返してほしいなら`coefficient`
+impl Index<usize> for Poly {
+    type Output = ModInt;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.coef[index]
+    }
+}
+
+/// `x^k`の係数
+///
+/// 次数を超える`k`には0を返す
+pub fn coefficient(poly: &Poly, k: usize) -> ModInt {
+    poly.coef
+        .get(k)
+        .cloned()
+        .unwrap_or_else(|| ModInt::new(0, poly.modulus))
+}
+
+impl<'a> Add for &'a Poly {
+    type Output = Poly;
+    fn add(self, rhs: Self) -> Self::Output {
+        let len = self.len().max(rhs.len());
+        let res = (0..len)
+            .map(|i| coefficient(self, i) + coefficient(rhs, i))
+            .collect();
+        Poly::new(res, self.modulus)
+    }
+}
+
+impl<'a> Sub for &'a Poly {
+    type Output = Poly;
+    fn sub(self, rhs: Self) -> Self::Output {
+        let len = self.len().max(rhs.len());
+        let res = (0..len)
+            .map(|i| coefficient(self, i) - coefficient(rhs, i))
+            .collect();
+        Poly::new(res, self.modulus)
+    }
+}
+
+/// NTTが使えるmodならNTT、そうでなければKaratsuba法
+impl<'a> Mul for &'a Poly {
+    type Output = Poly;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Poly::new(convolution(&self.coef, &rhs.coef), self.modulus)
+    }
+}
+
+/// これ以下の長さでは筆算の方が速い、`ntt::convolution`がNTTに切り替える長さでもある
+pub(crate) const KARATSUBA_THRESHOLD: usize = 32;
+
+fn schoolbook(a: &[ModInt], b: &[ModInt]) -> Vec<ModInt> {
+    let mut res = vec![ModInt::new(0, a[0].get_mod()); a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            res[i + j] += x * y;
+        }
+    }
+    res
+}
+
+/// 多項式の積(畳み込み)をKaratsuba法で
+///
+/// NTTが使えないmodでの中くらいの大きさの積に、`O(n^1.59)`
+///
+/// # Panic
+/// `a`と`b`のmodが異なる時
+pub fn karatsuba(a: &[ModInt], b: &[ModInt]) -> Vec<ModInt> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    if a.len().min(b.len()) <= KARATSUBA_THRESHOLD {
+        return schoolbook(a, b);
+    }
+    // (a0 + a1 x^h)(b0 + b1 x^h)
+    //   = z0 + ((a0 + a1)(b0 + b1) - z0 - z2) x^h + z2 x^2h
+    let half = (a.len().min(b.len()) + 1) / 2;
+    let (a0, a1) = a.split_at(half);
+    let (b0, b1) = b.split_at(half);
+    let z0 = karatsuba(a0, b0);
+    let z2 = karatsuba(a1, b1);
+    let padded_sum = |lo: &[ModInt], hi: &[ModInt]| {
+        let mut s = lo.to_vec();
+        s.resize(lo.len().max(hi.len()), ModInt::new(0, lo[0].get_mod()));
+        for (x, &y) in s.iter_mut().zip(hi.iter()) {
+            *x += y;
+        }
+        s
+    };
+    let mut z1 = karatsuba(&padded_sum(a0, a1), &padded_sum(b0, b1));
+    for (x, &y) in z1.iter_mut().zip(z0.iter()) {
+        *x -= y;
+    }
+    for (x, &y) in z1.iter_mut().zip(z2.iter()) {
+        *x -= y;
+    }
+
+    let len = a.len() + b.len() - 1;
+    let mut res = vec![ModInt::new(0, a[0].get_mod()); len];
+    for (i, &x) in z0.iter().enumerate() {
+        res[i] += x;
+    }
+    for (i, &x) in z1.iter().enumerate().take(len - half) {
+        res[i + half] += x;
+    }
+    for (i, &x) in z2.iter().enumerate() {
+        res[i + 2 * half] += x;
+    }
+    res
+}
+
+/// `f(g(x)) mod x^n`
+///
+/// Brent–Kung: `k = ceil(sqrt(deg f + 1))`として
+/// `g^0, ..., g^(k-1)`と`G = g^k`を前計算し、
+/// `f`を`k`個ずつの係数のブロックに分けて`G`についてのHornerで組み立てる
+///
+/// 乗算が素朴な`O(n^2)`なので全体で`O(sqrt(deg f) n^2)`
+pub fn composition(f: &Poly, g: &Poly, n: usize) -> Poly {
+    let modulus = f.modulus;
+    if n == 0 || f.is_empty() {
+        return Poly::zero(modulus);
+    }
+    let mut width = 1;
+    while width * width < f.len() {
+        width += 1;
+    }
+
+    // baby[i] = g^i mod x^n
+    let mut baby = Vec::with_capacity(width + 1);
+    baby.push(Poly::one(modulus).truncate(n));
+    for i in 0..width {
+        let next = baby[i].mul_trunc(g, n);
+        baby.push(next);
+    }
+    let giant = baby.pop().unwrap();
+
+    let mut res = Poly::zero(modulus);
+    for block in f.coef.chunks(width).rev() {
+        // block(g) = sum_i block[i] * g^i
+        let mut acc = vec![ModInt::new(0, modulus); n];
+        for (&c, p) in block.iter().zip(baby.iter()) {
+            for (a, &b) in acc.iter_mut().zip(p.coef.iter()) {
+                *a += c * b;
+            }
+        }
+        res = &res.mul_trunc(&giant, n) + &Poly::new(acc, modulus);
+    }
+    res
+}
+
+#[test]
+fn coefficient_test() {
+    let p = Poly::from_slice(&[1, 2, 3, 0, 0], 7);
+    assert_eq!(p.degree(), Some(2));
+    assert_eq!(coefficient(&p, 1).get(), 2);
+    assert_eq!(coefficient(&p, 2), p[2]);
+    assert_eq!(coefficient(&p, 3).get(), 0);
+    assert_eq!(coefficient(&p, 100).get(), 0);
+    assert_eq!(Poly::from_slice(&[0, 7], 7).degree(), None);
+}
+
+#[test]
+fn poly_arith_test() {
+    let m = 998_244_353;
+    let a = Poly::from_slice(&[1, 1], m);
+    let b = Poly::from_slice(&[-1, 1], m);
+    // (1 + x)(x - 1) = x^2 - 1
+    assert_eq!(&a * &b, Poly::from_slice(&[-1, 0, 1], m));
+    assert_eq!(&a + &b, Poly::from_slice(&[0, 2], m));
+    assert_eq!(&(&a + &b) - &Poly::from_slice(&[0, 2], m), Poly::zero(m));
+    assert_eq!((&a * &a).eval(ModInt::new(3, m)).get(), 16);
+}
+
+#[test]
+fn composition_test() {
+    let m = 998_244_353;
+    let f = Poly::from_slice(&[1, 2, 3, 4, 5, 6, 7], m);
+    let g = Poly::from_slice(&[0, 1, 1], m);
+
+    // 直接代入: sum f_i g^i
+    let mut naive = Poly::zero(m);
+    let mut pw = Poly::one(m);
+    for &c in f.coef() {
+        naive = &naive + &(&pw * &Poly::new(vec![c], m));
+        pw = &pw * &g;
+    }
+
+    let n = 13;
+    let fg = composition(&f, &g, n);
+    assert_eq!(fg, naive);
+    for &n in &[0, 1, 2, 5, 8] {
+        assert_eq!(composition(&f, &g, n), naive.truncate(n));
+    }
+
+    // 定数項のあるgでも、点での値が一致する
+    let g = Poly::from_slice(&[3, -2, 5], m);
+    let fg = composition(&f, &g, 13);
+    for t in 0..10 {
+        let t = ModInt::new(t, m);
+        assert_eq!(fg.eval(t), f.eval(g.eval(t)));
+    }
+}
+
+#[test]
+fn karatsuba_test() {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    let mut rng = SmallRng::seed_from_u64(410);
+    for &m in &[1_000_000_007u32, 998_244_353, 4_294_967_291] {
+        for _ in 0..20 {
+            let la = rng.gen_range(1, 200);
+            let lb = rng.gen_range(1, 200);
+            let a = (0..la)
+                .map(|_| ModInt::new(rng.gen_range(0, m), m))
+                .collect::<Vec<_>>();
+            let b = (0..lb)
+                .map(|_| ModInt::new(rng.gen_range(0, m), m))
+                .collect::<Vec<_>>();
+            assert_eq!(karatsuba(&a, &b), schoolbook(&a, &b));
+        }
+    }
+    assert!(karatsuba(&[], &[ModInt::new(1, 7)]).is_empty());
+
+    // Polyの積はmodに応じて自動で選ぶ
+    let m = 1_000_000_007;
+    let a = Poly::from_slice(&(0..100).collect::<Vec<_>>(), m);
+    let b = Poly::from_slice(&(0..80).map(|i| i * i - 7).collect::<Vec<_>>(), m);
+    assert_eq!(&a * &b, a.mul_trunc(&b, std::usize::MAX));
+}
@@ -1,2 +1,3 @@
+pub mod coprime_count;
 pub mod sieve;
 pub mod trial_division;
@@ -0,0 +1,52 @@
+use super::trial_division::factorization;
+
+/// `[1, n]`のうち`m`と互いに素な整数の個数
+///
+/// `m`の相異なる素因数についての包除原理で、素因数の個数を`k`として`O(sqrt m + 2^k)`
+///
+/// `n = m`ならオイラーのφ関数
+pub fn coprime_count(n: u64, m: u64) -> u64 {
+    let primes = factorization(m as usize)
+        .into_iter()
+        .map(|(p, _)| p as u64)
+        .collect::<Vec<_>>();
+    // n > i64::MAX でも溢れないよう i128 で
+    let mut res = 0i128;
+    for mask in 0..1usize << primes.len() {
+        let mut prod = 1u64;
+        for (i, &p) in primes.iter().enumerate() {
+            if mask >> i & 1 == 1 {
+                prod *= p;
+            }
+        }
+        let cnt = (n / prod) as i128;
+        if mask.count_ones() % 2 == 0 {
+            res += cnt;
+        } else {
+            res -= cnt;
+        }
+    }
+    res as u64
+}
+
+#[test]
+fn coprime_count_test() {
+    let naive = |n: u64, m: u64| {
+        use num::Integer;
+        (1..=n).filter(|i| i.gcd(&m) == 1).count() as u64
+    };
+    // n = m ならφ(m)
+    assert_eq!(coprime_count(12, 12), 4);
+    assert_eq!(coprime_count(1_000_000_007, 1_000_000_007), 1_000_000_006);
+    assert_eq!(coprime_count(30, 30), 8);
+
+    for m in 1..60 {
+        for n in 0..100 {
+            assert_eq!(coprime_count(n, m), naive(n, m), "n = {}, m = {}", n, m);
+        }
+    }
+    assert_eq!(coprime_count(1_000_000_000_000, 6), 333_333_333_333);
+    assert_eq!(coprime_count(std::u64::MAX, 6), 6_148_914_691_236_517_205);
+    assert_eq!(coprime_count(std::u64::MAX, 2), 1 << 63);
+    assert_eq!(coprime_count(std::u64::MAX, 1), std::u64::MAX);
+}
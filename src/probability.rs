@@ -0,0 +1,103 @@
+use crate::modint::ModInt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+/// 確率を素数mod上の値として持つ
+///
+/// 確率`p/q`は`p * q^(-1) mod M`で表す (「答えを`p/q mod M`で出力せよ」の形式)
+///
+/// 確率DPで`ModInt`をそのまま使うと意味が読み取りにくいので、名前をつけておく
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Probability(ModInt);
+
+impl Probability {
+    /// 確率1
+    pub fn certain(modulus: u32) -> Self {
+        Probability(ModInt::new(1, modulus))
+    }
+
+    /// 確率0
+    pub fn impossible(modulus: u32) -> Self {
+        Probability(ModInt::new(0, modulus))
+    }
+
+    /// 確率`num / den`
+    ///
+    /// # Panic
+    /// `den`がmodで割り切れる時
+    pub fn of(num: i64, den: i64, modulus: u32) -> Self {
+        Probability(ModInt::from_fraction(num, den, modulus))
+    }
+
+    /// 余事象の確率 `1 - p`
+    pub fn complement(self) -> Self {
+        Probability(ModInt::new(1, self.0.get_mod()) - self.0)
+    }
+
+    /// modでの値
+    pub fn get(self) -> ModInt {
+        self.0
+    }
+}
+
+impl From<Probability> for ModInt {
+    fn from(p: Probability) -> Self {
+        p.0
+    }
+}
+
+macro_rules! impl_probability_ops {
+    ($($tr:ident, $f:ident, $tra:ident, $fa:ident);*) => {
+        $(
+            impl $tr for Probability {
+                type Output = Self;
+                fn $f(self, rhs: Self) -> Self::Output {
+                    Probability((self.0).$f(rhs.0))
+                }
+            }
+
+            impl $tra for Probability {
+                fn $fa(&mut self, rhs: Self) {
+                    *self = (*self).$f(rhs);
+                }
+            }
+        )*
+    };
+}
+
+// 排反な事象の和、独立な事象の積、条件付き確率の割り算
+impl_probability_ops!(
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign;
+    Div, div, DivAssign, div_assign
+);
+
+#[test]
+fn probability_test() {
+    let m = 998_244_353;
+    let half = Probability::of(1, 2, m);
+    let third = Probability::of(1, 3, m);
+
+    // 独立な事象がともに起こる
+    assert_eq!(half * third, Probability::of(1, 6, m));
+    // 排反な事象のどちらかが起こる
+    assert_eq!(half + third, Probability::of(5, 6, m));
+    assert_eq!(half + half, Probability::certain(m));
+    assert_eq!(Probability::of(5, 6, m) - third, half);
+    assert_eq!(third - Probability::of(1, 3, m), Probability::impossible(m));
+    assert_eq!(third.complement(), Probability::of(2, 3, m));
+    assert_eq!(
+        Probability::certain(m).complement(),
+        Probability::impossible(m)
+    );
+    // P(A | B) = P(A かつ B) / P(B)
+    assert_eq!((half * third) / third, half);
+
+    // コインを3回投げて少なくとも1回表
+    let mut all_tails = Probability::certain(m);
+    for _ in 0..3 {
+        all_tails *= half;
+    }
+    assert_eq!(all_tails.complement(), Probability::of(7, 8, m));
+    assert_eq!(ModInt::from(Probability::of(1, 2, m)).get(), 499_122_177);
+}
@@ -0,0 +1,106 @@
+//! 乱数で選んだ`ModInt`について環の公理が成り立つことを確かめる
+
+use crate::modint::{random_unit, ModInt};
+use rand::{rngs::SmallRng, SeedableRng};
+
+const ITER: usize = 10_000;
+
+/// 大きいmodでは積が`i64`を溢れうるので、`u32`に収まる最大の素数も含める
+const MODULI: [u32; 4] = [998_244_353, 1_000_000_007, 4_294_967_291, 97];
+
+fn triples(seed: u64, m: u32) -> impl Iterator<Item = (ModInt, ModInt, ModInt)> {
+    let mut rng = SmallRng::seed_from_u64(seed);
+    (0..ITER).map(move |_| {
+        let mut gen = || ModInt::random(&mut rng, m);
+        (gen(), gen(), gen())
+    })
+}
+
+#[test]
+fn add_assoc_comm_test() {
+    for &m in &MODULI {
+        for (a, b, c) in triples(1, m) {
+            assert_eq!((a + b) + c, a + (b + c));
+            assert_eq!(a + b, b + a);
+        }
+    }
+}
+
+#[test]
+fn mul_assoc_comm_test() {
+    for &m in &MODULI {
+        for (a, b, c) in triples(2, m) {
+            assert_eq!((a * b) * c, a * (b * c));
+            assert_eq!(a * b, b * a);
+        }
+    }
+}
+
+#[test]
+fn distributive_test() {
+    for &m in &MODULI {
+        for (a, b, c) in triples(3, m) {
+            assert_eq!(a * (b + c), a * b + a * c);
+            assert_eq!((a + b) * c, a * c + b * c);
+            assert_eq!(a * (b - c), a * b - a * c);
+        }
+    }
+}
+
+#[test]
+fn additive_inverse_test() {
+    for &m in &MODULI {
+        for (a, b, _) in triples(4, m) {
+            assert_eq!((a + (-a)).get(), 0);
+            assert_eq!(a - b, a + (-b));
+            assert_eq!(a + ModInt::new(0, m), a);
+        }
+    }
+}
+
+#[test]
+fn multiplicative_inverse_test() {
+    for &m in &MODULI {
+        for (a, b, _) in triples(5, m) {
+            assert_eq!(a * ModInt::new(1, m), a);
+            if a.get() == 0 {
+                continue;
+            }
+            assert_eq!((a * ModInt::new(a.inv(), m)).get(), 1);
+            assert_eq!(a * a.checked_inv().unwrap(), ModInt::new(1, m));
+            assert_eq!(b / a * a, b);
+        }
+    }
+}
+
+#[test]
+fn composite_modulus_inverse_test() {
+    // 合成数のmodでは互いに素なものだけ逆元を持つ
+    let m = 1_000_000_000;
+    for (a, b, c) in triples(6, m) {
+        assert_eq!((a + b) * c, a * c + b * c);
+        match a.checked_inv() {
+            Some(inv) => assert_eq!(a * inv, ModInt::new(1, m)),
+            None => assert!(a.get() % 2 == 0 || a.get() % 5 == 0),
+        }
+    }
+
+    // 互いに素なものだけを選んで a * a.inv() == 1
+    let mut rng = SmallRng::seed_from_u64(7);
+    for &m in &[
+        1_000_000_000u32,
+        12,
+        30,
+        1 << 20,
+        999_999_999,
+        4_294_967_295,
+    ] {
+        for _ in 0..ITER {
+            let a = random_unit(&mut rng, m);
+            assert_eq!((a * ModInt::new(a.inv(), m)).get(), 1);
+            assert_eq!(a * a.checked_inv().unwrap(), ModInt::new(1, m));
+            let b = ModInt::random(&mut rng, m);
+            assert_eq!(b / a * a, b);
+        }
+    }
+}
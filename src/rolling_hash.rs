@@ -0,0 +1,104 @@
+use crate::binary_search::binary_search;
+use crate::modint::ModInt;
+
+/// ローリングハッシュ
+///
+/// 初期化 `O(n)`
+/// 部分文字列のハッシュ `O(1)`
+#[derive(Debug, Clone)]
+pub struct RollingHash {
+    base: usize,
+    modulo: usize,
+    /// `hash[i]` = `[0..i)`のハッシュ
+    hash: Vec<ModInt>,
+    /// `power[i]` = `base^i`
+    power: Vec<ModInt>,
+}
+
+impl RollingHash {
+    pub fn new<T: Copy + Into<u32>>(s: &[T], base: usize, modulo: usize) -> Self {
+        let mut hash = Vec::with_capacity(s.len() + 1);
+        let mut power = Vec::with_capacity(s.len() + 1);
+        hash.push(ModInt::new(0, modulo));
+        power.push(ModInt::new(1, modulo));
+        for (i, &c) in s.iter().enumerate() {
+            let c: u32 = c.into();
+            hash.push(hash[i] * base + c);
+            power.push(power[i] * base);
+        }
+        Self {
+            base,
+            modulo,
+            hash,
+            power,
+        }
+    }
+
+    /// 元の列の長さ
+    pub fn len(&self) -> usize {
+        self.hash.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `[from..to)`のハッシュ
+    pub fn hash(&self, from: usize, to: usize) -> ModInt {
+        self.hash[to] - self.hash[from] * self.power[to - from]
+    }
+}
+
+/// `a`の`ai`文字目からと`b`の`bi`文字目からの最長共通接頭辺の長さ
+///
+/// ハッシュの一致を二分探索するので `O(log n)`
+///
+/// 開始位置が範囲外なら空文字列として扱い0を返す
+///
+/// # Panic
+/// `a`と`b`で`base`かmodが異なる時 (ハッシュを比べられない)
+pub fn longest_common_prefix(a: &RollingHash, b: &RollingHash, ai: usize, bi: usize) -> usize {
+    assert!(
+        a.base == b.base && a.modulo == b.modulo,
+        "rolling hashes must share base and modulo"
+    );
+    if ai >= a.len() || bi >= b.len() {
+        return 0;
+    }
+    let max = (a.len() - ai).min(b.len() - bi);
+    binary_search(|x| a.hash(ai, ai + x) == b.hash(bi, bi + x), 0, max).unwrap()
+}
+
+#[test]
+fn rolling_hash_test() {
+    let rh = RollingHash::new(b"abcabc", 10007, 1_000_000_007);
+    assert_eq!(rh.len(), 6);
+    assert_eq!(rh.hash(0, 3), rh.hash(3, 6));
+    assert_ne!(rh.hash(0, 2), rh.hash(1, 3));
+}
+
+#[test]
+fn lcp_test() {
+    // 同じ文字列の重なりあう接尾辞同士
+    let a = RollingHash::new(b"abababc", 10007, 1_000_000_007);
+    assert_eq!(longest_common_prefix(&a, &a, 0, 2), 4);
+    assert_eq!(longest_common_prefix(&a, &a, 1, 3), 3);
+    assert_eq!(longest_common_prefix(&a, &a, 0, 0), 7);
+
+    // 別々の文字列
+    let b = RollingHash::new(b"abax", 10007, 1_000_000_007);
+    assert_eq!(longest_common_prefix(&a, &b, 0, 0), 3);
+    assert_eq!(longest_common_prefix(&a, &b, 1, 0), 0);
+    let c = RollingHash::new(b"xyz", 10007, 1_000_000_007);
+    assert_eq!(longest_common_prefix(&a, &c, 0, 0), 0);
+
+    // 範囲外
+    assert_eq!(longest_common_prefix(&a, &b, 7, 0), 0);
+    assert_eq!(longest_common_prefix(&a, &b, 100, 100), 0);
+
+    // baseかmodが異なるハッシュ同士は比べられない
+    let other_base = RollingHash::new(b"abax", 10009, 1_000_000_007);
+    assert!(std::panic::catch_unwind(|| longest_common_prefix(&a, &other_base, 0, 0)).is_err());
+    let other_mod = RollingHash::new(b"abax", 10007, 998_244_353);
+    assert!(std::panic::catch_unwind(|| longest_common_prefix(&a, &other_mod, 0, 0)).is_err());
+}
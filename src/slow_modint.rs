@@ -0,0 +1,152 @@
+//! 最適化した`ModInt`の演算を確かめるための、素朴な実装との差分テスト
+
+use crate::barrett::BarrettReducer;
+use crate::modint::ModInt;
+use num_traits::Pow;
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use std::ops::{Add, Div, Mul, Sub};
+
+/// 演算ごとに`i128`で剰余をとるだけの`ModInt`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlowModInt {
+    v: i128,
+    m: i128,
+}
+
+impl SlowModInt {
+    pub fn new(v: i128, m: u32) -> Self {
+        let m = m as i128;
+        Self {
+            v: ((v % m) + m) % m,
+            m,
+        }
+    }
+
+    pub fn get(self) -> i64 {
+        self.v as i64
+    }
+
+    pub fn pow(self, mut exp: u64) -> Self {
+        let mut res = Self::new(1, self.m as u32);
+        let mut base = self;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res = res * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+        res
+    }
+
+    /// フェルマーの小定理で、modは素数であること
+    pub fn inv(self) -> Self {
+        self.pow(self.m as u64 - 2)
+    }
+}
+
+impl Add for SlowModInt {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.v + rhs.v, self.m as u32)
+    }
+}
+
+impl Sub for SlowModInt {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.v - rhs.v, self.m as u32)
+    }
+}
+
+impl Mul for SlowModInt {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(self.v * rhs.v, self.m as u32)
+    }
+}
+
+/// modは素数であること
+impl Div for SlowModInt {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        self.mul(rhs.inv())
+    }
+}
+
+/// 乱数で選んだ`steps`回の演算を`ModInt`と`SlowModInt`の両方で行い、毎回一致を確かめる
+///
+/// modは素数であること
+pub fn assert_matches_slow(seed: u64, steps: usize, modulus: u32) {
+    let mut rng = SmallRng::seed_from_u64(seed);
+    let barrett = BarrettReducer::new(modulus);
+    let mut fast = ModInt::new(1, modulus);
+    let mut slow = SlowModInt::new(1, modulus);
+    for step in 0..steps {
+        let r: i64 = rng.gen();
+        let (f, s) = (ModInt::new(r, modulus), SlowModInt::new(r as i128, modulus));
+        let op = rng.gen_range(0, 9);
+        match op {
+            0 => {
+                fast += f;
+                slow = slow + s;
+            }
+            1 => {
+                fast -= f;
+                slow = slow - s;
+            }
+            2 => {
+                fast *= f;
+                slow = slow * s;
+            }
+            3 if s.v != 0 => {
+                fast /= f;
+                slow = slow / s;
+            }
+            4 => {
+                let e = rng.gen_range(0, 1_000_000usize);
+                fast = fast.pow(e);
+                slow = slow.pow(e as u64);
+            }
+            5 => {
+                let u: u64 = rng.gen();
+                fast += u;
+                slow = slow + SlowModInt::new(u as i128, modulus);
+            }
+            6 => {
+                let u: u64 = rng.gen();
+                fast -= u;
+                slow = slow - SlowModInt::new(u as i128, modulus);
+            }
+            7 => {
+                fast *= r;
+                slow = slow * s;
+            }
+            8 => {
+                fast = barrett.mul_mint(fast, f);
+                slow = slow * s;
+            }
+            _ => {}
+        }
+        if slow.v == 0 {
+            // 0に落ちたら抜け出せないので戻す
+            fast = ModInt::new(r | 1, modulus);
+            slow = SlowModInt::new((r | 1) as i128, modulus);
+        }
+        assert_eq!(
+            fast.get(),
+            slow.get(),
+            "diverged at step {} (op {})",
+            step,
+            op
+        );
+    }
+}
+
+#[test]
+fn differential_test() {
+    assert_matches_slow(401, 10_000, 998_244_353);
+    assert_matches_slow(402, 10_000, 1_000_000_007);
+    assert_matches_slow(403, 10_000, 4_294_967_291);
+    assert_matches_slow(404, 10_000, 13);
+}
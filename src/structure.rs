@@ -2,6 +2,9 @@ pub mod accumulate;
 pub mod delayed_segment_tree;
 pub mod fenwick_tree;
 pub mod generic_fenwick_tree;
+pub mod mod_fenwick;
+pub mod mod_lazy_segment_tree;
+pub mod mod_segment_tree;
 pub mod segment_tree;
 pub mod union_find;
 
@@ -0,0 +1,114 @@
+use crate::modint::ModInt;
+
+/// `ModInt`の和を持つFenwick Tree
+///
+/// 全要素が同じmodを共有する
+///
+/// 一点加算、prefixの和がともに`O(log n)`
+#[derive(Debug, Clone)]
+pub struct ModFenwick {
+    len: usize,
+    modulus: u32,
+    segment: Vec<ModInt>,
+}
+
+impl ModFenwick {
+    /// サイズを指定して、0で初期化
+    pub fn new(n: usize, modulus: u32) -> Self {
+        Self {
+            len: n,
+            modulus,
+            segment: vec![ModInt::new(0, modulus); n + 1],
+        }
+    }
+
+    /// 配列から構築
+    ///
+    /// `O(n)`
+    pub fn from_slice(v: &[ModInt], modulus: u32) -> Self {
+        let mut res = Self::new(v.len(), modulus);
+        for (i, &x) in v.iter().enumerate() {
+            let i = i + 1;
+            res.segment[i] += x;
+            let parent = i + (i & i.wrapping_neg());
+            if parent <= res.len {
+                let s = res.segment[i];
+                res.segment[parent] += s;
+            }
+        }
+        res
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn modulus(&self) -> u32 {
+        self.modulus
+    }
+
+    /// `i`番目の要素 += `v`
+    ///
+    /// # Panic
+    /// `v`のmodが異なる時
+    pub fn add(&mut self, i: usize, v: ModInt) {
+        let mut i = i + 1;
+        while i <= self.len {
+            self.segment[i] += v;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// `[0..i)`の和
+    pub fn sum(&self, mut i: usize) -> ModInt {
+        let mut s = ModInt::new(0, self.modulus);
+        while i > 0 {
+            s += self.segment[i];
+            i -= i & i.wrapping_neg();
+        }
+        s
+    }
+
+    /// `[from..to)`の和
+    pub fn partial_sum(&self, from: usize, to: usize) -> ModInt {
+        self.sum(to) - self.sum(from)
+    }
+}
+
+#[test]
+fn mod_fenwick_test() {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    let m = 1_000_000_007;
+    let n = 50;
+    let mut rng = SmallRng::seed_from_u64(386);
+    let mut naive = vec![ModInt::new(0, m); n];
+    let mut f = ModFenwick::new(n, m);
+    for _ in 0..500 {
+        let i = rng.gen_range(0, n);
+        let v = ModInt::new(rng.gen_range(0, 2_000_000_000u64), m);
+        naive[i] += v;
+        f.add(i, v);
+
+        let to = rng.gen_range(0, n + 1);
+        let expected = naive[..to].iter().fold(ModInt::new(0, m), |a, &b| a + b);
+        assert_eq!(f.sum(to), expected);
+    }
+    for from in 0..=n {
+        for to in from..=n {
+            let expected = naive[from..to]
+                .iter()
+                .fold(ModInt::new(0, m), |a, &b| a + b);
+            assert_eq!(f.partial_sum(from, to), expected);
+        }
+    }
+
+    let g = ModFenwick::from_slice(&naive, m);
+    for i in 0..=n {
+        assert_eq!(g.sum(i), f.sum(i));
+    }
+}
@@ -0,0 +1,196 @@
+use crate::modint::ModInt;
+
+/// 区間アフィン変換 `x -> a x + b` と区間和を扱う遅延伝播セグメント木
+///
+/// 全要素が同じmodを共有する
+///
+/// 区間更新、区間和がともに`O(log n)`
+#[derive(Debug, Clone)]
+pub struct ModLazySegTree {
+    len: usize,
+    /// 葉の数(2冪)
+    size: usize,
+    modulus: u32,
+    /// `sum[k]`は節点`k`の区間の和
+    sum: Vec<ModInt>,
+    /// `lazy[k] = (a, b)`は子にまだ伝えていない変換 `x -> a x + b`
+    lazy: Vec<(ModInt, ModInt)>,
+}
+
+impl ModLazySegTree {
+    /// `O(n)`
+    ///
+    /// `Dynamic`な要素は`modulus`に揃える
+    ///
+    /// # Panic
+    /// `v`にmodが`modulus`と異なる要素がある時
+    pub fn new(v: &[ModInt], modulus: u32) -> Self {
+        let len = v.len();
+        let size = len.next_power_of_two();
+        let zero = ModInt::new(0, modulus);
+        let mut sum = vec![zero; 2 * size];
+        for (i, &x) in v.iter().enumerate() {
+            // 0を足してmodを揃える、異なるmodならpanic
+            sum[size + i] = zero + x;
+        }
+        for k in (1..size).rev() {
+            sum[k] = sum[2 * k] + sum[2 * k + 1];
+        }
+        Self {
+            len,
+            size,
+            modulus,
+            sum,
+            lazy: vec![(ModInt::new(1, modulus), zero); 2 * size],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn modulus(&self) -> u32 {
+        self.modulus
+    }
+
+    /// 幅`width`の節点`k`に`x -> a x + b`を作用させる
+    fn effect(&mut self, k: usize, width: usize, (a, b): (ModInt, ModInt)) {
+        self.sum[k] = a * self.sum[k] + b * width;
+        // 既にある (a1, b1) の後に (a, b): x -> a(a1 x + b1) + b
+        let (a1, b1) = self.lazy[k];
+        self.lazy[k] = (a * a1, a * b1 + b);
+    }
+
+    fn push(&mut self, k: usize, width: usize) {
+        let f = self.lazy[k];
+        if f.0.get() == 1 && f.1.get() == 0 {
+            return;
+        }
+        self.effect(2 * k, width / 2, f);
+        self.effect(2 * k + 1, width / 2, f);
+        self.lazy[k] = (ModInt::new(1, self.modulus), ModInt::new(0, self.modulus));
+    }
+
+    fn apply_rec(
+        &mut self,
+        k: usize,
+        l: usize,
+        r: usize,
+        from: usize,
+        to: usize,
+        f: (ModInt, ModInt),
+    ) {
+        if to <= l || r <= from {
+            return;
+        }
+        if from <= l && r <= to {
+            self.effect(k, r - l, f);
+            return;
+        }
+        self.push(k, r - l);
+        let mid = (l + r) / 2;
+        self.apply_rec(2 * k, l, mid, from, to, f);
+        self.apply_rec(2 * k + 1, mid, r, from, to, f);
+        self.sum[k] = self.sum[2 * k] + self.sum[2 * k + 1];
+    }
+
+    fn sum_rec(&mut self, k: usize, l: usize, r: usize, from: usize, to: usize) -> ModInt {
+        if to <= l || r <= from {
+            return ModInt::new(0, self.modulus);
+        }
+        if from <= l && r <= to {
+            return self.sum[k];
+        }
+        self.push(k, r - l);
+        let mid = (l + r) / 2;
+        self.sum_rec(2 * k, l, mid, from, to) + self.sum_rec(2 * k + 1, mid, r, from, to)
+    }
+
+    /// `[from..to)`の各要素を`a x + b`にする
+    ///
+    /// # Panic
+    /// `a`, `b`のmodが異なる時
+    pub fn apply(&mut self, from: usize, to: usize, a: ModInt, b: ModInt) {
+        assert!(from <= to && to <= self.len, "invalid range");
+        let zero = ModInt::new(0, self.modulus);
+        let size = self.size;
+        self.apply_rec(1, 0, size, from, to, (zero + a, zero + b));
+    }
+
+    /// `[from..to)`の和、空区間なら0
+    ///
+    /// 遅延させた変換を伝えるので`&mut self`
+    pub fn sum(&mut self, from: usize, to: usize) -> ModInt {
+        assert!(from <= to && to <= self.len, "invalid range");
+        let size = self.size;
+        self.sum_rec(1, 0, size, from, to)
+    }
+
+    pub fn get(&mut self, i: usize) -> ModInt {
+        assert!(i < self.len, "index out of range");
+        self.sum(i, i + 1)
+    }
+}
+
+#[test]
+fn mod_lazy_segment_tree_test() {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    let m = 998_244_353;
+    let n = 29;
+    let mut rng = SmallRng::seed_from_u64(463);
+    let mut naive = (0..n)
+        .map(|_| ModInt::new(rng.gen_range(0, m), m))
+        .collect::<Vec<_>>();
+    let mut st = ModLazySegTree::new(&naive, m);
+    assert_eq!(st.len(), n);
+    for _ in 0..500 {
+        // 重なりあう区間へのアフィン変換
+        let from = rng.gen_range(0, n + 1);
+        let to = rng.gen_range(from, n + 1);
+        let a = ModInt::new(rng.gen_range(0, m), m);
+        let b = ModInt::new(rng.gen_range(0, m), m);
+        st.apply(from, to, a, b);
+        for x in &mut naive[from..to] {
+            *x = a * *x + b;
+        }
+
+        let from = rng.gen_range(0, n + 1);
+        let to = rng.gen_range(from, n + 1);
+        let expected = naive[from..to]
+            .iter()
+            .fold(ModInt::new(0, m), |acc, &e| acc + e);
+        assert_eq!(st.sum(from, to), expected);
+    }
+    for (i, &x) in naive.iter().enumerate() {
+        assert_eq!(st.get(i), x);
+    }
+
+    // 区間に足してから区間を2倍
+    let mut st = ModLazySegTree::new(&[ModInt::new(1, 13); 5], 13);
+    st.apply(0, 3, ModInt::new(1, 13), ModInt::new(2, 13));
+    st.apply(2, 5, ModInt::new(2, 13), ModInt::new(0, 13));
+    // [3, 3, 6, 2, 2]
+    assert_eq!(st.sum(0, 5).get(), 16 % 13);
+    assert_eq!(st.sum(1, 3).get(), 9);
+    assert_eq!(st.sum(2, 2).get(), 0);
+    assert_eq!(ModLazySegTree::new(&[], m).sum(0, 0).get(), 0);
+
+    // Dynamicな値はmodに揃え、異なるmodは共通のメッセージでpanic
+    let mut st = ModLazySegTree::new(&["1000".parse().unwrap(), ModInt::new(2, 7)], 7);
+    st.apply(0, 2, "3".parse().unwrap(), "10".parse().unwrap());
+    // [6 * 3 + 3, 2 * 3 + 3] = [0, 2]
+    assert_eq!(st.get(0).get(), 0);
+    assert_eq!(st.sum(0, 2).get(), 2);
+    let err =
+        std::panic::catch_unwind(move || st.apply(0, 1, ModInt::new(1, 11), ModInt::new(0, 7)))
+            .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<String>().unwrap(),
+        "modulus mismatch: lhs mod 7 vs rhs mod 11"
+    );
+}
@@ -0,0 +1,136 @@
+use crate::modint::ModInt;
+
+/// `ModInt`の区間積を持つセグメント木
+///
+/// 全要素が同じmodを共有する
+///
+/// 一点更新、区間積がともに`O(log n)`
+#[derive(Debug, Clone)]
+pub struct ModSegTree {
+    len: usize,
+    /// 葉の数(2冪)
+    size: usize,
+    modulus: u32,
+    /// `segment[size + i]`が`i`番目の要素、`segment[k] = segment[2k] * segment[2k + 1]`
+    segment: Vec<ModInt>,
+}
+
+impl ModSegTree {
+    /// `O(n)`
+    ///
+    /// `Dynamic`な要素は`modulus`に揃える
+    ///
+    /// # Panic
+    /// `v`にmodが`modulus`と異なる要素がある時
+    pub fn new(v: &[ModInt], modulus: u32) -> Self {
+        let len = v.len();
+        let size = len.next_power_of_two();
+        let mut segment = vec![ModInt::new(1, modulus); 2 * size];
+        for (i, &x) in v.iter().enumerate() {
+            // 0を足してmodを揃える、異なるmodならpanic
+            segment[size + i] = ModInt::new(0, modulus) + x;
+        }
+        for k in (1..size).rev() {
+            segment[k] = segment[2 * k] * segment[2 * k + 1];
+        }
+        Self {
+            len,
+            size,
+            modulus,
+            segment,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn modulus(&self) -> u32 {
+        self.modulus
+    }
+
+    pub fn get(&self, i: usize) -> ModInt {
+        assert!(i < self.len, "index out of range");
+        self.segment[self.size + i]
+    }
+
+    /// `i`番目の要素を`x`にする
+    ///
+    /// # Panic
+    /// `x`のmodが異なる時
+    pub fn update(&mut self, i: usize, x: ModInt) {
+        assert!(i < self.len, "index out of range");
+        let mut k = self.size + i;
+        self.segment[k] = ModInt::new(0, self.modulus) + x;
+        while k > 1 {
+            k /= 2;
+            self.segment[k] = self.segment[2 * k] * self.segment[2 * k + 1];
+        }
+    }
+
+    /// `[from..to)`の積、空区間なら1
+    pub fn prod(&self, from: usize, to: usize) -> ModInt {
+        assert!(from <= to && to <= self.len, "invalid range");
+        let mut res = ModInt::new(1, self.modulus);
+        let (mut l, mut r) = (from + self.size, to + self.size);
+        while l < r {
+            if l & 1 == 1 {
+                res *= self.segment[l];
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                res *= self.segment[r];
+            }
+            l /= 2;
+            r /= 2;
+        }
+        res
+    }
+}
+
+#[test]
+fn mod_segment_tree_test() {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    let m = 998_244_353;
+    let n = 37;
+    let mut rng = SmallRng::seed_from_u64(414);
+    let mut naive = (0..n)
+        .map(|_| ModInt::new(rng.gen_range(0, m), m))
+        .collect::<Vec<_>>();
+    let mut st = ModSegTree::new(&naive, m);
+    assert_eq!(st.len(), n);
+    for _ in 0..500 {
+        let i = rng.gen_range(0, n);
+        // たまに0を入れる
+        let x = ModInt::new(rng.gen_range(0, m) * rng.gen_range(0, 2), m);
+        naive[i] = x;
+        st.update(i, x);
+        assert_eq!(st.get(i), x);
+
+        let from = rng.gen_range(0, n + 1);
+        let to = rng.gen_range(from, n + 1).max(from);
+        let expected = naive[from..to]
+            .iter()
+            .fold(ModInt::new(1, m), |acc, &e| acc * e);
+        assert_eq!(st.prod(from, to), expected);
+    }
+    assert_eq!(st.prod(3, 3).get(), 1);
+    assert_eq!(ModSegTree::new(&[], m).prod(0, 0).get(), 1);
+
+    // Dynamicな値はmodに揃え、異なるmodは共通のメッセージでpanic
+    let mut st = ModSegTree::new(&["1000".parse().unwrap(), ModInt::new(2, 7)], 7);
+    assert_eq!(st.get(0), ModInt::new(6, 7));
+    st.update(1, "10".parse().unwrap());
+    assert_eq!(st.prod(0, 2).get(), 4);
+    let err = std::panic::catch_unwind(move || st.update(0, ModInt::new(1, 11))).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<String>().unwrap(),
+        "modulus mismatch: lhs mod 7 vs rhs mod 11"
+    );
+}